@@ -1,13 +1,15 @@
 use axum::{response::IntoResponse, routing::get, Router};
 use playground::{routes, state::WebHtmxState};
-use std::{net::SocketAddr, sync::Arc};
+use std::net::SocketAddr;
 
 mod playground;
 
 #[tokio::main]
 async fn main() {
     let web_htmx_state = WebHtmxState {
-        flash_config: axum_flash::Config::new(axum_flash::Key::generate()),
+        // Plain http in development.
+        flash_config: axum_flash::Config::new(axum_flash::Key::generate())
+            .use_secure_cookies(false),
     };
 
     let app = Router::new()
@@ -1,10 +1,13 @@
-use axum::{http::StatusCode, middleware, response::Html, routing::get, Router};
+use axum::{middleware, response::Html, routing::get, Router};
+use htmx_components::server::notification::flash::flash_notifications;
 use rscx::{component, html, props};
 
+use data_table::{data_table_routes, DataTablePlayground};
 use file_input::{file_input_routes, FileInputPlayground};
 use form::FormPlayground;
 use html_element::HtmlElementPlayground;
 use htmx::{htmx_routes, HtmxPlayground};
+use inline_edit::{inline_edit_routes, InlineEditPlayground};
 use modal::{modal_routes, ModalPlayground};
 use notifications::{notification_routes, NotificationsPlayground};
 use page::{page_routes, PagePlayground};
@@ -15,10 +18,12 @@ use self::{context::provide_context_layer, state::WebHtmxState};
 
 pub mod appshell;
 pub mod context;
+pub mod data_table;
 pub mod file_input;
 pub mod form;
 pub mod html_element;
 pub mod htmx;
+pub mod inline_edit;
 pub mod modal;
 pub mod notifications;
 pub mod page;
@@ -32,8 +37,14 @@ pub fn routes(state: WebHtmxState) -> Router {
         .nest("/page", page_routes())
         .nest("/htmx", htmx_routes())
         .nest("/modals", modal_routes())
-        .nest("/notifications", notification_routes())
+        .nest("/notifications", notification_routes(state.clone()))
         .nest("/file-input", file_input_routes())
+        .nest("/inline-edit", inline_edit_routes())
+        .nest("/data-table", data_table_routes())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            flash_notifications,
+        ))
         .layer(middleware::from_fn_with_state(state, provide_context_layer))
 }
 
@@ -62,6 +73,8 @@ pub fn PlaygroundPgContent() -> String {
         <ModalPlayground />
         <FileInputPlayground />
         <FormPlayground />
+        <InlineEditPlayground />
+        <DataTablePlayground />
         <HtmxPlayground />
         <PagePlayground />
         <HtmlElementPlayground />
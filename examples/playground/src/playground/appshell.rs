@@ -1,16 +1,16 @@
 use htmx_components::server::page_header::PageHeaderToolbar;
 use rscx::{component, html, props};
 
+#[derive(Default)]
 pub enum PageHeader {
+    #[default]
     None,
     Title(String),
-    Toolbar { title: String, buttons: String },
-}
-
-impl Default for PageHeader {
-    fn default() -> Self {
-        Self::None
-    }
+    #[allow(dead_code)]
+    Toolbar {
+        title: String,
+        buttons: String,
+    },
 }
 
 impl From<String> for PageHeader {
@@ -21,7 +21,7 @@ tokio::task_local! {
 }
 
 pub async fn provide_context_layer(
-    State(state): State<WebHtmxState>,
+    State(_state): State<WebHtmxState>,
     request: Request<Body>,
     next: Next,
 ) -> Response {
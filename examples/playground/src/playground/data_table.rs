@@ -0,0 +1,261 @@
+use axum::{
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+    Router,
+};
+use axum_extra::extract::Form;
+use futures_util::stream;
+use rscx::{component, html, props};
+use serde::Deserialize;
+
+use htmx_components::server::{
+    attrs::Attrs,
+    confirm::ConfirmVariant,
+    notification::{NotificationCall, NotificationPresenter},
+    table::{
+        data_table::{Column, ColumnAlign, DataTable},
+        export::{export_response, export_stream_response, ExportScope},
+        query::{SortDirection, TableExport, TableQuery},
+        table_body, BulkActionButton, BulkSelect, Confirm, TDVariant, Table, TableData,
+        TableHeading, TableRow, TableRowAction, TableRowSelect,
+    },
+};
+use rscx_web_macros::TableRow;
+
+pub fn data_table_routes() -> Router {
+    Router::new()
+        .route("/", get(get_data_table))
+        .route("/bulk-delete", post(post_bulk_delete))
+}
+
+#[derive(Clone, TableRow)]
+#[table_row(actions = planet_actions)]
+pub struct Planet {
+    pub name: &'static str,
+    pub moons: u32,
+    pub kind: &'static str,
+}
+
+fn planet_actions(planet: &Planet) -> Vec<TableRowAction> {
+    vec![TableRowAction::delete(
+        "Explode",
+        format!(", {}", planet.name),
+        Confirm::new(
+            format!("Explode {}?", planet.name),
+            "This can not be undone.",
+        )
+        .confirm_label("Explode")
+        .type_to_confirm(planet.name),
+        Attrs::with("hx-delete", "/playground/modals/foo".into()),
+    )]
+}
+
+pub fn planets() -> Vec<Planet> {
+    vec![
+        Planet {
+            name: "Mercury",
+            moons: 0,
+            kind: "Terrestrial",
+        },
+        Planet {
+            name: "Venus",
+            moons: 0,
+            kind: "Terrestrial",
+        },
+        Planet {
+            name: "Earth",
+            moons: 1,
+            kind: "Terrestrial",
+        },
+        Planet {
+            name: "Mars",
+            moons: 2,
+            kind: "Terrestrial",
+        },
+        Planet {
+            name: "Jupiter",
+            moons: 95,
+            kind: "Gas giant",
+        },
+        Planet {
+            name: "Saturn",
+            moons: 146,
+            kind: "Gas giant",
+        },
+        Planet {
+            name: "Uranus",
+            moons: 28,
+            kind: "Ice giant",
+        },
+        Planet {
+            name: "Neptune",
+            moons: 16,
+            kind: "Ice giant",
+        },
+    ]
+}
+
+// Our "database queries" for the playground.
+pub fn sorted_planets(query: &TableQuery) -> Vec<Planet> {
+    let mut planets = planets();
+
+    match query.sort.as_deref() {
+        Some("name") => planets.sort_by_key(|planet| planet.name),
+        Some("moons") => planets.sort_by_key(|planet| planet.moons),
+        _ => {}
+    }
+    if query.dir == SortDirection::Desc {
+        planets.reverse();
+    }
+
+    planets
+}
+
+pub fn query_planets(query: &TableQuery) -> (Vec<Planet>, usize) {
+    let planets = sorted_planets(query);
+
+    let total = planets.len();
+    let page = planets
+        .into_iter()
+        .skip(query.offset())
+        .take(query.per_page)
+        .collect();
+
+    (page, total)
+}
+
+pub fn planet_columns() -> Vec<Column<Planet>> {
+    vec![
+        Column::new("Name", |planet: &Planet| planet.name.to_string()).sortable("name"),
+        Column::new("Type", |planet: &Planet| planet.kind.to_string()).cell(|planet: &Planet| {
+            let kind = planet.kind;
+            async move {
+                html! {
+                    <span class="inline-flex items-center rounded-md bg-gray-50 px-2 py-1 text-xs font-medium text-gray-600 ring-1 ring-inset ring-gray-500/10">
+                        {kind}
+                    </span>
+                }
+            }
+        }),
+        Column::new("Moons", |planet: &Planet| planet.moons.to_string())
+            .sortable("moons")
+            .align(ColumnAlign::Right)
+            .width("w-24"),
+    ]
+}
+
+// ### Route Handlers ###
+
+async fn get_data_table(query: TableQuery) -> Response {
+    match query.export {
+        Some(TableExport {
+            format,
+            scope: ExportScope::Page,
+        }) => {
+            let (rows, _) = query_planets(&query);
+            export_response(format, "planets", &planet_columns(), &rows)
+        }
+        Some(TableExport {
+            format,
+            scope: ExportScope::All,
+        }) => export_stream_response(
+            format,
+            "planets",
+            planet_columns(),
+            stream::iter(sorted_planets(&query)),
+        ),
+        None => Html(html! {
+            <PlanetsTable query=query />
+        })
+        .into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+struct BulkForm {
+    #[serde(default)]
+    ids: Vec<String>,
+}
+
+async fn post_bulk_delete(Form(form): Form<BulkForm>) -> Html<String> {
+    Html(html! {
+        <NotificationPresenter
+            call=NotificationCall::Success(format!("Exploded {}!", form.ids.join(", ")))
+        />
+    })
+}
+
+// ### Components ###
+
+#[component]
+fn BulkPlanetsTable() -> String {
+    let mut body = vec![];
+    for planet in planets() {
+        body.push(html! {
+            <TableRowSelect value=planet.name sr_text=format!("Select {}", planet.name) />
+            <TableData variant=TDVariant::First>{planet.name}</TableData>
+            <TableData variant=TDVariant::LastNonEmptyHeading>{planet.kind}</TableData>
+        });
+    }
+
+    html! {
+        <BulkSelect
+            id="bulk-planets"
+            actions=html! {
+                <BulkActionButton
+                    hx_post="/playground/data-table/bulk-delete"
+                    hx_target="body"
+                    hx_swap="beforeend"
+                    confirm=Some(
+                        Confirm::new("Explode planets?", "All selected planets will be exploded.")
+                            .variant(ConfirmVariant::Warning)
+                            .confirm_label("Explode all"),
+                    )
+                >
+                    Explode
+                </BulkActionButton>
+            }
+        >
+            <Table
+                headings=vec![
+                    TableHeading::select_all("Select all planets"),
+                    TableHeading::title("Name"),
+                    TableHeading::title("Type"),
+                ]
+                body=body
+            />
+        </BulkSelect>
+    }
+}
+
+#[component]
+fn PlanetsTable(query: TableQuery) -> String {
+    let (rows, total) = query_planets(&query);
+
+    html! {
+        <DataTable
+            id="planets-table"
+            columns=planet_columns()
+            rows=rows
+            total=total
+            query=query
+            url="/playground/data-table"
+            exportable=true
+        />
+    }
+}
+
+#[component]
+pub fn DataTablePlayground() -> String {
+    html! {
+        <section class="py-8">
+            <h2 class="text-xl font-bold">DataTable Playground</h2>
+            <p><em>Sort by clicking a column header, page through or export with the footer.</em></p>
+            <PlanetsTable query=TableQuery { per_page: 5, ..Default::default() } />
+            <p class="mt-4"><em>Or derive the rows with <code>"#[derive(TableRow)]"</code>.</em></p>
+            <Table headings=Planet::table_headings() body=table_body(&planets()).await />
+            <p class="mt-4"><em>Select rows to apply a bulk action.</em></p>
+            <BulkPlanetsTable />
+        </section>
+    }
+}
@@ -1,7 +1,11 @@
 use axum::{response::Html, routing::get, Router};
 use rscx::{component, html, props};
 
-use htmx_components::server::button::SecondaryButton;
+use htmx_components::server::attrs::Attrs;
+use htmx_components::server::button::{
+    Button, ButtonGroup, ButtonSegment, ButtonVariant, SecondaryButton, SplitButton,
+};
+use htmx_components::server::popup_menu::{MenuEntry, MenuLink};
 
 pub fn htmx_routes() -> Router {
     Router::new().route("/", get(htmx_test))
@@ -27,7 +31,49 @@ pub fn HtmxPlayground() -> String {
                 >
                     Click me!
                 </SecondaryButton>
+                <Button
+                    variant=ButtonVariant::Ghost
+                    loading=true
+                    hx_get="/playground/htmx"
+                    hx_swap="outerHTML"
+                >
+                    Load with spinner
+                </Button>
+            </div>
+            <div class="mt-4 flex gap-2">
+                <ButtonGroup
+                    label="Range"
+                    hx_target="#htmx-range"
+                    segments=["Day", "Week", "Month"]
+                        .into_iter()
+                        .map(|range| {
+                            ButtonSegment::builder()
+                                .label(range)
+                                .hx_get("/playground/htmx")
+                                .active(range == "Day")
+                                .build()
+                        })
+                        .collect()
+                />
+                <SplitButton
+                    menu_id="htmx-split-button"
+                    hx_get="/playground/htmx"
+                    hx_target="#htmx-range"
+                    entries=vec![
+                        MenuEntry::from(
+                            MenuLink::builder()
+                                .label("Save as draft")
+                                .attrs(Attrs::with("hx-get", "/playground/htmx".into()))
+                                .build(),
+                        ),
+                        MenuEntry::Divider,
+                        MenuLink::from(("Discard".to_string(), "#".to_string())).into(),
+                    ]
+                >
+                    Save
+                </SplitButton>
             </div>
+            <p id="htmx-range" class="mt-2 text-sm text-gray-500"></p>
         </section>
     }
 }
@@ -0,0 +1,110 @@
+use axum::{response::Html, routing::get, Form, Router};
+use rscx::{component, html, props};
+use serde::Deserialize;
+use std::sync::Mutex;
+
+use htmx_components::server::{
+    inline_edit::{InlineEdit, InlineEditForm},
+    table::{TDVariant, Table, TableData, TableHeading},
+};
+
+// Poor man's DB for the playground.
+static NICKNAME: Mutex<String> = Mutex::new(String::new());
+
+fn nickname() -> String {
+    let nickname = NICKNAME.lock().unwrap();
+    if nickname.is_empty() {
+        "Mary Sue".into()
+    } else {
+        nickname.clone()
+    }
+}
+
+pub fn inline_edit_routes() -> Router {
+    Router::new()
+        .route("/nickname", get(get_nickname).put(put_nickname))
+        .route("/nickname/edit", get(get_nickname_edit))
+}
+
+// ### Route Handlers ###
+
+async fn get_nickname() -> Html<String> {
+    Html(html! {
+        <NicknameRead />
+    })
+}
+
+async fn get_nickname_edit() -> Html<String> {
+    Html(html! {
+        <NicknameEdit value=nickname() />
+    })
+}
+
+#[derive(Deserialize)]
+struct NicknameForm {
+    nickname: String,
+}
+
+async fn put_nickname(Form(form): Form<NicknameForm>) -> Html<String> {
+    if form.nickname.trim().is_empty() {
+        return Html(html! {
+            <NicknameEdit
+                value=form.nickname
+                error=Some("Nickname can not be empty.".into())
+            />
+        });
+    }
+
+    *NICKNAME.lock().unwrap() = form.nickname;
+
+    Html(html! {
+        <NicknameRead />
+    })
+}
+
+// ### Components ###
+
+#[component]
+fn NicknameRead() -> String {
+    html! {
+        <InlineEdit
+            id="inline-edit-nickname"
+            value=nickname()
+            edit_url="/playground/inline-edit/nickname/edit"
+            sr_text="Edit nickname"
+        />
+    }
+}
+
+#[component]
+fn NicknameEdit(value: String, #[builder(default)] error: Option<String>) -> String {
+    html! {
+        <InlineEditForm
+            id="inline-edit-nickname"
+            name="nickname"
+            value=value
+            save_url="/playground/inline-edit/nickname"
+            cancel_url="/playground/inline-edit/nickname"
+            error=error
+        />
+    }
+}
+
+#[component]
+pub fn InlineEditPlayground() -> String {
+    html! {
+        <section class="py-8">
+            <h2 class="text-xl font-bold">Inline Edit Playground</h2>
+            <p><em>Hover a cell and click the pencil to edit it in place.</em></p>
+            <Table
+                headings=vec![TableHeading::title("Name"), TableHeading::title("Nickname")]
+                body=vec![html! {
+                    <TableData variant=TDVariant::First>Mary Sue Smith</TableData>
+                    <TableData variant=TDVariant::LastNonEmptyHeading>
+                        <NicknameRead />
+                    </TableData>
+                }]
+            />
+        </section>
+    }
+}
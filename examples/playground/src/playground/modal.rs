@@ -1,34 +1,150 @@
 #![allow(unused_braces)]
 use axum::{
-    response::Html,
-    routing::{delete, get},
-    Router,
+    http::HeaderMap,
+    response::{Html, IntoResponse, Response},
+    routing::{delete, get, post},
+    Form, Router,
 };
 use htmx_components::server::{
-    button::PrimaryButton,
-    flyout::Flyout,
-    modal::Modal,
-    notification::{NotificationCall, NotificationPresenter},
+    attrs::Attrs,
+    button::{PrimaryButton, SecondaryButton},
+    confirm::{Confirm, ConfirmVariant},
+    flyout::{Flyout, FlyoutSide, FlyoutSize},
+    form::{Label, TextInput},
+    modal::{
+        close_modal, open_modal, refresh_event, route::push_modal_route, Modal, ModalForm,
+        ModalFormBody, ModalFormResponse,
+    },
+    notification::{Notification, NotificationCall, NotificationLevel, NotificationPresenter},
 };
 use rscx::{component, html, props};
+use serde::Deserialize;
+use std::{sync::Mutex, time::Duration};
+
+// Poor man's DB for the playground.
+static CONTACTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
 
 pub fn modal_routes() -> Router {
     Router::new()
         .route("/modal-one", get(get_modal_one))
+        .route("/modal-slots", get(get_modal_slots))
+        .route("/server-modal", post(post_server_modal))
+        .route("/server-modal/save", post(post_server_modal_save))
+        .route("/contacts", get(get_contacts).post(post_contacts))
+        .route("/contacts/new", get(get_contacts_new))
         .route("/flyout-one", get(get_flyout_one))
+        .route("/flyout-filters", get(get_flyout_filters))
         .route("/foo", delete(delete_foo))
+        .route("/foo/undo", post(post_foo_undo))
 }
 
 // ### Route Handlers ###
 
-async fn get_modal_one() -> Html<String> {
+async fn get_modal_one(headers: HeaderMap) -> impl IntoResponse {
+    (
+        push_modal_route(&headers, "/playground/modals/modal-one"),
+        Html(html! {
+            <Modal title_id="modal-one-title" show_close=true>
+                <h1 id="modal-one-title">I am a very boring and simple modal!</h1>
+                <p class="mt-2 text-sm text-gray-500">Share the URL to open me right away.</p>
+            </Modal>
+        }),
+    )
+}
+
+async fn get_modal_slots() -> Html<String> {
     Html(html! {
-        <Modal>
-            <h1>I am a very boring and simple modal!</h1>
+        <Modal
+            title="Modal with slots"
+            description="Header, close button and footer come with the modal."
+            show_close=true
+            footer=html! {
+                <PrimaryButton attrs=Attrs::with("data-toggle-action", "close".into())>
+                    Got it
+                </PrimaryButton>
+                <SecondaryButton attrs=Attrs::with("data-toggle-action", "close".into())>
+                    Cancel
+                </SecondaryButton>
+                <SecondaryButton hx_post="/playground/modals/server-modal" hx_swap="none">
+                    Open Another
+                </SecondaryButton>
+            }
+        >
+            <p class="text-sm text-gray-700">Everything in between is up to you.</p>
+        </Modal>
+    })
+}
+
+async fn post_server_modal() -> Response {
+    open_modal(html! {
+        <Modal
+            title="Opened by the server"
+            show_close=true
+            footer=html! {
+                <PrimaryButton
+                    hx_post="/playground/modals/server-modal/save"
+                    hx_target="body"
+                    hx_swap="beforeend"
+                >
+                    Save
+                </PrimaryButton>
+            }
+        >
+            <p class="text-sm text-gray-700">Saving closes this modal from the server.</p>
         </Modal>
     })
 }
 
+async fn post_server_modal_save() -> impl IntoResponse {
+    (
+        close_modal(),
+        Html(html! {
+            <NotificationPresenter call=NotificationCall::Success("Saved!".into()) />
+        }),
+    )
+}
+
+async fn get_contacts() -> Html<String> {
+    Html(html! {
+        <ContactList />
+    })
+}
+
+async fn get_contacts_new() -> Html<String> {
+    Html(html! {
+        <ModalForm title="Add contact" hx_post="/playground/modals/contacts">
+            <ContactFields />
+        </ModalForm>
+    })
+}
+
+#[derive(Deserialize)]
+struct ContactForm {
+    name: String,
+}
+
+async fn post_contacts(Form(form): Form<ContactForm>) -> Response {
+    if form.name.trim().is_empty() {
+        return ModalFormResponse::Invalid(html! {
+            <ModalFormBody hx_post="/playground/modals/contacts">
+                <ContactFields name=form.name error=Some("Name can not be empty.".into()) />
+            </ModalFormBody>
+        })
+        .respond()
+        .await;
+    }
+
+    let message = format!("Added {}!", form.name);
+    CONTACTS.lock().unwrap().push(form.name);
+
+    ModalFormResponse::Success {
+        refresh: Some("contacts".into()),
+        notification: Some(NotificationCall::Success(message)),
+    }
+    .respond()
+    .await
+}
+
 async fn get_flyout_one() -> Html<String> {
     Html(html! {
         <Flyout title="Hello Playground!">
@@ -37,14 +153,81 @@ async fn get_flyout_one() -> Html<String> {
     })
 }
 
-async fn delete_foo() -> Html<String> {
+async fn get_flyout_filters() -> Html<String> {
     Html(html! {
-        <NotificationPresenter call=NotificationCall::Success("Deleted Foo!".into()) />
+        <Flyout
+            title="Filters"
+            description="Narrow down the list."
+            side=FlyoutSide::Left
+            size=FlyoutSize::Small
+            overlay=true
+            footer=html! {
+                <SecondaryButton attrs=Attrs::with("data-toggle-action", "close".into())>
+                    Cancel
+                </SecondaryButton>
+                <PrimaryButton attrs=Attrs::with("data-toggle-action", "close".into())>
+                    Apply
+                </PrimaryButton>
+            }
+        >
+            <Label for_input="search">Search</Label>
+            <div class="mt-2">
+                <TextInput name="search" />
+            </div>
+        </Flyout>
     })
 }
 
+async fn delete_foo() -> impl IntoResponse {
+    let notification = Notification::new(NotificationLevel::Success, "Deleted Foo!")
+        .timeout(Duration::from_secs(8))
+        .action("Undo", "/playground/modals/foo/undo");
+
+    (
+        NotificationCall::Notification(notification).to_hx_trigger(),
+        (),
+    )
+}
+
+async fn post_foo_undo() -> impl IntoResponse {
+    (
+        NotificationCall::Info("Undone".into(), "Foo is back.".into()).to_hx_trigger(),
+        (),
+    )
+}
+
 // ### Components ###
 
+#[component]
+fn ContactFields(
+    #[builder(default)] name: String,
+    #[builder(default)] error: Option<String>,
+) -> String {
+    html! {
+        <Label for_input="name" error=error.is_some()>Name</Label>
+        <div class="mt-2">
+            <TextInput name="name" value=name error=error />
+        </div>
+    }
+}
+
+#[component]
+fn ContactList() -> String {
+    let contacts = CONTACTS.lock().unwrap().join(", ");
+
+    html! {
+        <p
+            class="text-sm text-gray-700"
+            hx-get="/playground/modals/contacts"
+            hx-trigger=format!("{} from:body", refresh_event("contacts"))
+            hx-swap="outerHTML"
+        >
+            "Contacts: "
+            {if contacts.is_empty() { "none yet".to_string() } else { contacts }}
+        </p>
+    }
+}
+
 #[component]
 pub fn ModalPlayground() -> String {
     html! {
@@ -60,12 +243,53 @@ pub fn ModalPlayground() -> String {
                         >
                             Open Simple Modal
                         </PrimaryButton>
+                        <PrimaryButton
+                            hx_get="/playground/modals/modal-slots"
+                            hx_target="#modals-root"
+                        >
+                            Open Modal With Slots
+                        </PrimaryButton>
+                        <PrimaryButton
+                            hx_post="/playground/modals/server-modal"
+                            hx_swap="none"
+                        >
+                            Open Modal From Server
+                        </PrimaryButton>
                         <PrimaryButton
                             hx_get="/playground/modals/flyout-one"
                             hx_target="#modals-root"
                         >
                             Open Flyout
                         </PrimaryButton>
+                        <PrimaryButton
+                            hx_get="/playground/modals/flyout-filters"
+                            hx_target="#modals-root"
+                        >
+                            Open Filters Flyout
+                        </PrimaryButton>
+                        <SecondaryButton
+                            hx_delete="/playground/modals/foo"
+                            hx_swap="none"
+                            confirm=Some(
+                                Confirm::new("Delete Foo?", "Foo will be gone for good.")
+                                    .variant(ConfirmVariant::Neutral)
+                                    .cancel_label("Keep Foo"),
+                            )
+                        >
+                            Delete Foo
+                        </SecondaryButton>
+                    </div>
+                </section>
+                <section>
+                    <p><em>Submit a form in a modal, then refresh a list.</em></p>
+                    <div class="flex items-center gap-4">
+                        <PrimaryButton
+                            hx_get="/playground/modals/contacts/new"
+                            hx_target="#modals-root"
+                        >
+                            Add Contact
+                        </PrimaryButton>
+                        <ContactList />
                     </div>
                 </section>
             </div>
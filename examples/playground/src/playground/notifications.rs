@@ -1,59 +1,64 @@
 use axum::{
-    response::Html,
+    extract::Query,
+    response::{Html, IntoResponse},
     routing::{get, post},
     Router,
 };
+use axum_flash::Flash;
 use rscx::{component, html, props};
+use serde::Deserialize;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::state::WebHtmxState;
 use htmx_components::server::{
     attrs::Attrs,
     button::{PrimaryButton, SecondaryButton},
     notification::{
-        NoticationCloseButton, NotificationCall, NotificationPresenter, NotificationTransition,
+        NoticationCloseButton, Notification, NotificationCall, NotificationLevel,
+        NotificationPresenter, NotificationTransition,
     },
 };
 
-pub fn notification_routes() -> Router {
+pub fn notification_routes(state: WebHtmxState) -> Router {
     Router::new()
         .route("/business-logic-example", post(post_business_logic))
         .route("/custom-1", get(get_custom_notification1))
         .route("/custom-2", get(get_custom_notification2))
+        .route("/header-notification", post(post_header_notification))
+        .route("/export-job", post(post_export_job))
+        .route("/flash-redirect", post(post_flash_redirect))
+        .with_state(state)
 }
 
 // ### Route Handlers ###
 
 async fn get_custom_notification1() -> Html<String> {
     Html(html! {
-        <NotificationPresenter call=NotificationCall::Template>
-            <template>
-                <div class="bg-white p-10 border">
-                    <p>This is a bad notification!</p>
-                    <SecondaryButton attrs=Attrs::with("data-toggle-action", "close".into())>
-                        Close me
-                    </SecondaryButton>
-                </div>
-            </template>
-        </NotificationPresenter>
+        <NotificationPresenter call=NotificationCall::Template(html! {
+            <div class="bg-white p-10 border">
+                <p>This is a bad notification!</p>
+                <SecondaryButton attrs=Attrs::with("data-toggle-action", "close".into())>
+                    Close me
+                </SecondaryButton>
+            </div>
+        }) />
     })
 }
 
 async fn get_custom_notification2() -> Html<String> {
     Html(html! {
-        <NotificationPresenter call=NotificationCall::Template>
-            <template>
-                <NotificationTransition
-                    class="bg-white border w-full max-w-sm overflow-hidden shadow-lg"
-                >
-                    <div class="p-4">
-                        <div class="flex items-start">
-                            <p class="flex-1">Wow this looks a lil nicer.</p>
-                            <NoticationCloseButton />
-                        </div>
+        <NotificationPresenter call=NotificationCall::Template(html! {
+            <NotificationTransition
+                class="bg-white border w-full max-w-sm overflow-hidden shadow-lg"
+            >
+                <div class="p-4">
+                    <div class="flex items-start">
+                        <p class="flex-1">Wow this looks a lil nicer.</p>
+                        <NoticationCloseButton />
                     </div>
-                </NotificationTransition>
-            </template>
-        </NotificationPresenter>
+                </div>
+            </NotificationTransition>
+        }) />
     })
 }
 
@@ -71,6 +76,58 @@ async fn post_business_logic() -> Html<String> {
     })
 }
 
+async fn post_header_notification() -> impl IntoResponse {
+    // No body to swap, the notification comes with the HX-Trigger header.
+    (
+        NotificationCall::Info("Heads up".into(), "Sent without touching the page.".into())
+            .to_hx_trigger(),
+        (),
+    )
+}
+
+#[derive(Deserialize)]
+struct ExportJobQuery {
+    #[serde(default)]
+    progress: u32,
+}
+
+async fn post_export_job(Query(query): Query<ExportJobQuery>) -> impl IntoResponse {
+    // Fakes a long running job, each step updates the same notification.
+    let notification = match query.progress {
+        0 => Notification::new(NotificationLevel::Info, "Export started…").sticky(),
+        progress if progress < 100 => {
+            Notification::new(NotificationLevel::Info, format!("{}%…", progress)).sticky()
+        }
+        _ => Notification::new(NotificationLevel::Success, "Done, your export is ready."),
+    }
+    .title("Export")
+    .id("export-job");
+
+    let next_step = match query.progress {
+        progress if progress < 100 => html! {
+            <div
+                hx-post=format!("/playground/notifications/export-job?progress={}", progress + 50)
+                hx-trigger="load delay:1s"
+                hx-swap="outerHTML"
+            ></div>
+        },
+        _ => String::new(),
+    };
+
+    (
+        NotificationCall::Notification(notification).to_hx_trigger(),
+        Html(next_step),
+    )
+}
+
+async fn post_flash_redirect(flash: Flash) -> impl IntoResponse {
+    // Shown on the page redirected to, see `flash_notifications`.
+    (
+        flash.info("Flashed across a redirect!"),
+        [("HX-Redirect", "/playground")],
+    )
+}
+
 // ### Components ###
 
 #[component]
@@ -83,20 +140,30 @@ pub fn NotificationsPlayground() -> String {
                     <p><em>Show a toast notification (client-side).</em></p>
                     <div class="flex gap-2">
                         <PrimaryButton
-                            onclick="YcControls.showSuccessNotification('Success feels so good!')"
+                            onclick="YcControls.notify({ level: 'success', title: 'Success', message: 'Success feels so good!' })"
                         >
                             Show Success
                         </PrimaryButton>
                         <PrimaryButton
-                            onclick="YcControls.showErrorNotification('This is an error notification.')"
+                            onclick="YcControls.notify({ level: 'error', title: 'Error', message: 'This is an error notification.' })"
                         >
                             Show Error
                         </PrimaryButton>
                         <PrimaryButton
-                            onclick="YcControls.showNotification('This just in', 'You are still not done!')"
+                            onclick="YcControls.notify({ level: 'info', title: 'This just in', message: 'You are still not done!' })"
                         >
                             Show Generic
                         </PrimaryButton>
+                        <PrimaryButton
+                            onclick="YcControls.notify({ level: 'warning', title: 'Warning', message: 'Disk space is running low.' })"
+                        >
+                            Show Warning
+                        </PrimaryButton>
+                        <PrimaryButton
+                            onclick="YcControls.notify({ level: 'info', title: 'Sticky', message: 'Stays until you close it.', timeout: null })"
+                        >
+                            Show Sticky
+                        </PrimaryButton>
                     </div>
                 </section>
                 <section>
@@ -107,6 +174,22 @@ pub fn NotificationsPlayground() -> String {
                         >
                             Show Success
                         </PrimaryButton>
+                        <PrimaryButton
+                            hx_post="/playground/notifications/header-notification"
+                            hx_swap="none"
+                        >
+                            Show From Header
+                        </PrimaryButton>
+                        <PrimaryButton
+                            hx_post="/playground/notifications/export-job"
+                            hx_target="body"
+                            hx_swap="beforeend"
+                        >
+                            Start Export
+                        </PrimaryButton>
+                        <PrimaryButton hx_post="/playground/notifications/flash-redirect">
+                            Flash And Redirect
+                        </PrimaryButton>
                         <PrimaryButton
                             hx_get="/playground/notifications/custom-1"
                             hx_target="body"
@@ -1,6 +1,12 @@
 use super::appshell::AppShell;
 pub use super::appshell::PageHeader;
-use htmx_components::server::{modal::ModalLiveRegion, notification::NotificationLiveRegion};
+use htmx_components::server::{
+    modal::{
+        route::{modal_param, ModalRoute},
+        ModalLiveRegion,
+    },
+    notification::NotificationLiveRegion,
+};
 use htmx_components::HtmlLayout;
 use rscx::{component, html, props};
 
@@ -38,24 +44,14 @@ pub fn PageLayout(props: PageLayoutProps) -> String {
                     <script>{
                         r#"
                         htmx.on("htmx:sendError", function() {
-                            YcControls.showErrorNotification("Network Error!");
+                            YcControls.notify({ level: "error", title: "Error", message: "Network Error!" });
                         });                
     
                         htmx.on("htmx:responseError", function(error) {
-                            YcControls.showErrorNotification(
-                                error.detail.xhr.responseText || "Unknown error"
-                            );
-                        });
-    
-                        document.addEventListener("htmx:confirm", function(e) {
-                            if (!e.target.hasAttribute("hx-confirm")) return true;            
-                            e.preventDefault();
-                            YcControls.confirm({
-                                title: e.target.getAttribute("hx-confirm"),
-                                message: e.target.dataset.confirmMessage,
-                                actionConfirmed: function() {
-                                    e.detail.issueRequest(true);
-                                }
+                            YcControls.notify({
+                                level: "error",
+                                title: "Error",
+                                message: error.detail.xhr.responseText || "Unknown error",
                             });
                         });
                         "#
@@ -68,38 +64,14 @@ pub fn PageLayout(props: PageLayoutProps) -> String {
                     {props.children}
                 </main>
             </AppShell>
-            <ModalProxy />
+            <ModalRoute
+                page_url=ctx.page_url
+                modal=modal_param(&ctx.page_query_params)
+            />
             <div hx-history-elt>
-                <NotificationLiveRegion />
+                <NotificationLiveRegion max_visible=Some(5) />
                 <ModalLiveRegion />
             </div>
         </HtmlLayout>
     }
 }
-
-#[component]
-fn ModalProxy() -> String {
-    let ctx: crate::playground::context::Context =
-        crate::playground::context::context().expect("Unable to retrieve htmx context.");
-
-    let query_params = ctx.page_query_params;
-
-    let modal = query_params
-        .get("modal")
-        .map(|s| if s.is_empty() { None } else { Some(s) });
-
-    match modal {
-        Some(Some(modal)) => {
-            let modal_url = if modal.starts_with('/') {
-                modal.to_owned()
-            } else {
-                format!("{}/{}", &ctx.page_url, &modal)
-            };
-
-            html! {
-                <div hx-get=modal_url hx-trigger="load" />
-            }
-        }
-        _ => html! { <></> },
-    }
-}
@@ -1,5 +1,4 @@
 use axum::extract::FromRef;
-use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct WebHtmxState {
@@ -3,6 +3,15 @@ extern crate self as htmx_components; // Allows components crate to import from
 pub use crate::server::html_layout::HtmlLayout;
 pub mod server;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+// Unique element id for components that reference their own parts (aria-labelledby...).
+pub fn unique_id(prefix: &str) -> String {
+    format!("{}-{}", prefix, NEXT_ID.fetch_add(1, Ordering::Relaxed))
+}
+
 pub fn concat_attribute(field_value: &str, attribute_value: Option<&String>) -> String {
     let mut values = vec![];
 
@@ -10,9 +19,17 @@ pub fn concat_attribute(field_value: &str, attribute_value: Option<&String>) ->
         values.push(field_value.trim());
     }
 
-    if let Some(value) = attribute_value {
+    if let Some(value) = attribute_value.filter(|value| !value.trim().is_empty()) {
         values.push(value.trim());
     }
 
     values.join(" ")
 }
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    // rscx escapes attribute values ("-" becomes "&#x2D;"...), decode them to assert on plain markup.
+    pub fn unescape(html: String) -> String {
+        rscx::html_escape::decode_html_entities(&html).into_owned()
+    }
+}
@@ -2,11 +2,14 @@ pub mod alert;
 pub mod attrs;
 pub mod button;
 pub mod card;
+pub mod confirm;
 pub mod flyout;
 pub mod form;
 pub mod headers;
 pub mod html_element;
 pub mod html_layout;
+pub mod hx_trigger;
+pub mod inline_edit;
 pub mod modal;
 pub mod notification;
 pub mod opt_attrs;
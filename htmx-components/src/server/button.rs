@@ -1,7 +1,13 @@
-use super::html_element::HtmlElement;
-use rscx::{component, html, props};
+use rscx::{component, html, props, CollectFragmentAsync};
+use typed_builder::TypedBuilder;
+
 use rscx_web_macros::*;
 
+use super::attrs::Attrs;
+use super::confirm::{with_confirm, Confirm};
+use super::html_element::HtmlElement;
+use super::popup_menu::{Menu, MenuAlign, MenuEntry, PopupMenu};
+
 #[derive(Clone)]
 pub enum ButtonSize {
     Xs,
@@ -11,44 +17,197 @@ pub enum ButtonSize {
     Xl,
 }
 
+impl ButtonSize {
+    fn class(&self, icon_only: bool) -> &'static str {
+        match (self, icon_only) {
+            (ButtonSize::Xs, false) => "rounded px-2 py-1 text-xs",
+            (ButtonSize::Sm, false) => "rounded px-2 py-1 text-sm",
+            (ButtonSize::Md, false) => "rounded-md px-2.5 py-1.5 text-sm",
+            (ButtonSize::Lg, false) => "rounded-md px-3 py-2 text-sm",
+            (ButtonSize::Xl, false) => "rounded-md px-3.5 py-2.5 text-sm",
+            (ButtonSize::Xs, true) => "rounded p-1 text-xs",
+            (ButtonSize::Sm, true) => "rounded p-1 text-sm",
+            (ButtonSize::Md, true) => "rounded-md p-1.5 text-sm",
+            (ButtonSize::Lg, true) => "rounded-md p-2 text-sm",
+            (ButtonSize::Xl, true) => "rounded-md p-2.5 text-sm",
+        }
+    }
+
+    fn text_class(&self) -> &'static str {
+        match self {
+            ButtonSize::Xs => "text-xs",
+            _ => "text-sm",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ButtonVariant {
+    #[default]
+    Primary,
+    Secondary,
+    Danger,
+    Ghost,
+    // Text only, e.g. "Cancel" next to a submit button.
+    Link,
+}
+
+impl ButtonVariant {
+    fn class(&self) -> &'static str {
+        match self {
+            ButtonVariant::Primary => "bg-indigo-600 text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
+            ButtonVariant::Secondary => "bg-white text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
+            ButtonVariant::Danger => "bg-red-600 text-white shadow-sm hover:bg-red-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-red-600",
+            ButtonVariant::Ghost => "text-gray-900 hover:bg-gray-100",
+            ButtonVariant::Link => "leading-6 text-gray-900 hover:text-gray-700",
+        }
+    }
+}
+
+/**
+ * Button
+ *
+ * Button (or link with `tag="a"`) in one of the `ButtonVariant` styles.
+ * `icon_label` makes it an icon-only button, the label is read to screen readers.
+ * With `loading`, it is disabled and shows a spinner while its htmx request runs
+ * (spinner needs the htmx loading-states extension).
+ */
 #[html_element]
-pub struct PrimaryButtonProps {
+pub struct ButtonProps {
+    #[builder(default)]
     children: String,
 
+    #[builder(default)]
+    variant: ButtonVariant,
+
     #[builder(default = ButtonSize::Md)]
     size: ButtonSize,
 
     #[builder(setter(into), default=String::from("button"))]
     tag: String,
 
+    // Type of the button, e.g. "submit".
+    #[builder(setter(into), default=String::from("button"))]
+    kind: String,
+
     #[builder(setter(into), default)]
     href: String,
+
+    #[builder(setter(into), default)]
+    leading_icon: String,
+
+    #[builder(setter(into), default)]
+    trailing_icon: String,
+
+    #[builder(setter(into), default)]
+    icon_label: String,
+
+    #[builder(default = false)]
+    disabled: bool,
+
+    #[builder(default = false)]
+    loading: bool,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
 }
 
 #[component]
-pub fn PrimaryButton(props: PrimaryButtonProps) -> String {
-    let tag = props.tag.clone();
-    let href = props.href.clone();
+pub fn Button(props: ButtonProps) -> String {
+    let is_button = props.tag == "button";
+    let icon_only = !props.icon_label.is_empty();
+    let size_class = match props.variant {
+        ButtonVariant::Link => props.size.text_class(),
+        _ => props.size.class(icon_only),
+    };
+    let class = format!(
+        "inline-flex items-center justify-center gap-x-1.5 font-semibold disabled:cursor-not-allowed disabled:opacity-50 aria-disabled:pointer-events-none aria-disabled:opacity-50 {} {} {}",
+        size_class,
+        props.variant.class(),
+        props.class,
+    );
+
+    let attrs = with_confirm(spread_attrs!(props | omit(class)), &props.confirm)
+        .set_if("type", props.kind.clone(), is_button)
+        .set_if("disabled", "disabled".into(), props.disabled && is_button)
+        .set_if("aria-disabled", "true".into(), props.disabled && !is_button)
+        // Disabled links don't go anywhere.
+        .set_if(
+            "href",
+            props.href.clone(),
+            !props.href.is_empty() && !is_button && !props.disabled,
+        );
+    let attrs = match props.loading {
+        true => attrs
+            .set("hx-disabled-elt", "this".into())
+            .set("data-loading-states", "true".into())
+            .set("data-loading-disable", "true".into())
+            .set("data-loading-aria-busy", "true".into()),
+        false => attrs,
+    };
 
     html! {
         <HtmlElement
             tag=props.tag
-            class={
-                let class = match props.size {
-                    ButtonSize::Xs => "rounded bg-indigo-600 px-2 py-1 text-xs font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
-                    ButtonSize::Sm => "rounded bg-indigo-600 px-2 py-1 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
-                    ButtonSize::Md => "rounded-md bg-indigo-600 px-2.5 py-1.5 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
-                    ButtonSize::Lg => "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
-                    ButtonSize::Xl => "rounded-md bg-indigo-600 px-3.5 py-2.5 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
-                };
-                format!("{} {}", class, props.class).trim()
+            class=class.trim()
+            attrs=attrs
+        >
+            {
+                if props.loading {
+                    html! {
+                        <svg class="hidden h-4 w-4 animate-spin" data-loading viewBox="0 0 24 24" fill="none" aria-hidden="true">
+                            <circle class="opacity-25" cx="12" cy="12" r="10" stroke="currentColor" stroke-width="4"></circle>
+                            <path class="opacity-75" fill="currentColor" d="M4 12a8 8 0 018-8V0C5.373 0 0 5.373 0 12h4z"></path>
+                        </svg>
+                    }
+                } else {
+                    String::new()
+                }
+            }
+            {props.leading_icon}
+            {
+                if icon_only {
+                    html! { <span class="sr-only">{props.icon_label}</span> }
+                } else {
+                    props.children
+                }
             }
+            {props.trailing_icon}
+        </HtmlElement>
+    }
+}
+
+#[html_element]
+pub struct PrimaryButtonProps {
+    children: String,
+
+    #[builder(default = ButtonSize::Md)]
+    size: ButtonSize,
+
+    #[builder(setter(into), default=String::from("button"))]
+    tag: String,
+
+    #[builder(setter(into), default)]
+    href: String,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
+}
+
+#[component]
+pub fn PrimaryButton(props: PrimaryButtonProps) -> String {
+    html! {
+        <Button
+            variant=ButtonVariant::Primary
+            size=props.size.clone()
+            tag=props.tag.clone()
+            href=props.href.clone()
+            confirm=props.confirm.clone()
+            class=props.class.clone()
             attrs=spread_attrs!(props | omit(class))
-                .set("type", "button".into())
-                .set_if("href", props.href, !href.is_empty() && tag == "a")
         >
             {props.children}
-        </HtmlElement>
+        </Button>
     }
 }
 
@@ -64,31 +223,317 @@ pub struct SecondaryButtonProps {
 
     #[builder(setter(into), default)]
     href: String,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
 }
 
 #[component]
 pub fn SecondaryButton(props: SecondaryButtonProps) -> String {
-    let tag = props.tag.clone();
-    let href = props.href.clone();
-
     html! {
-        <HtmlElement
-            tag=props.tag
-            class={
-                let class = match props.size {
-                    ButtonSize::Xs => "rounded bg-white px-2 py-1 text-xs font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
-                    ButtonSize::Sm => "rounded bg-white px-2 py-1 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
-                    ButtonSize::Md => "rounded-md bg-white px-2.5 py-1.5 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
-                    ButtonSize::Lg => "rounded-md bg-white px-3 py-2 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
-                    ButtonSize::Xl => "rounded-md bg-white px-3.5 py-2.5 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
-                };
-                format!("{} {}", class, props.class).trim()
-            }
+        <Button
+            variant=ButtonVariant::Secondary
+            size=props.size.clone()
+            tag=props.tag.clone()
+            href=props.href.clone()
+            confirm=props.confirm.clone()
+            class=props.class.clone()
             attrs=spread_attrs!(props | omit(class))
-                .set("type", "button".into())
-                .set_if("href", props.href, !href.is_empty() && tag == "a")
         >
             {props.children}
+        </Button>
+    }
+}
+
+// #### Button groups. ###############
+
+// Squares the inner corners of buttons placed side by side, so only the
+// outer corners of the group are rounded. Side utilities, as Tailwind emits
+// them after the `rounded`/`rounded-md` of the button size.
+fn segment_class(index: usize, count: usize) -> &'static str {
+    match (index, count) {
+        (_, 1) => "relative focus:z-10",
+        (0, _) => "relative focus:z-10 rounded-r-none",
+        (i, n) if i == n - 1 => "relative -ml-px focus:z-10 rounded-l-none",
+        _ => "relative -ml-px focus:z-10 rounded-l-none rounded-r-none",
+    }
+}
+
+// Active segments are styled by `aria-pressed`, so `BUTTON_GROUP_SCRIPT` only
+// has to move the attribute. Segments with a request move it once the request
+// succeeded, so a failed request keeps the state the server last applied.
+const ACTIVE_SEGMENT_CLASS: &str = "aria-pressed:bg-indigo-600 aria-pressed:text-white aria-pressed:ring-indigo-600 aria-pressed:hover:bg-indigo-500";
+
+pub(crate) const BUTTON_GROUP_SCRIPT: &str = r#"
+    (function() {
+        function press(segment) {
+            var group = segment.closest("[data-button-group]");
+            group.querySelectorAll("[data-button-segment]").forEach(function(other) {
+                other.setAttribute("aria-pressed", other === segment ? "true" : "false");
+            });
+        }
+
+        document.addEventListener("click", function(e) {
+            var segment = e.target.closest("[data-button-segment]");
+            if (!segment || segment.disabled || segment.hasAttribute("hx-get")) return;
+            press(segment);
+        });
+
+        document.addEventListener("htmx:afterRequest", function(e) {
+            var segment = e.detail.elt.closest && e.detail.elt.closest("[data-button-segment]");
+            if (segment && e.detail.successful) press(segment);
+        });
+    }());
+"#;
+
+#[derive(Clone, TypedBuilder)]
+pub struct ButtonSegment {
+    #[builder(setter(into))]
+    label: String,
+
+    #[builder(setter(into), default)]
+    hx_get: String,
+
+    #[builder(default = false)]
+    active: bool,
+
+    #[builder(default = false)]
+    disabled: bool,
+
+    #[builder(default=Attrs::default())]
+    attrs: Attrs,
+}
+
+impl From<(String, String)> for ButtonSegment {
+    fn from((label, hx_get): (String, String)) -> Self {
+        ButtonSegment::builder().label(label).hx_get(hx_get).build()
+    }
+}
+
+/**
+ * ButtonGroup
+ *
+ * Segmented control, e.g. "Day / Week / Month". Clicking a segment issues
+ * its `hx_get` and makes it the active one once the request succeeded.
+ */
+#[html_element]
+pub struct ButtonGroupProps {
+    segments: Vec<ButtonSegment>,
+
+    // Accessible name of the group.
+    #[builder(setter(into), default)]
+    label: String,
+
+    #[builder(default = ButtonSize::Md)]
+    size: ButtonSize,
+}
+
+#[component]
+pub fn ButtonGroup(props: ButtonGroupProps) -> String {
+    let count = props.segments.len();
+    let attrs = spread_attrs!(props | omit(class))
+        .set("role", "group".into())
+        .set("data-button-group", "true".into())
+        .set_if("aria-label", props.label.clone(), !props.label.is_empty());
+
+    html! {
+        <HtmlElement
+            tag="span"
+            class=format!("isolate inline-flex rounded-md shadow-sm {}", props.class).trim()
+            component_name="ButtonGroup"
+            attrs=attrs
+        >
+            {
+                props
+                    .segments
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, segment)| {
+                        let size = props.size.clone();
+                        async move {
+                            let attrs = segment
+                                .attrs
+                                .set("data-button-segment", "true".into())
+                                .set("aria-pressed", segment.active.to_string())
+                                .set_if("hx-get", segment.hx_get.clone(), !segment.hx_get.is_empty());
+
+                            html! {
+                                <Button
+                                    variant=ButtonVariant::Secondary
+                                    size=size
+                                    disabled=segment.disabled
+                                    class=format!("{} {}", segment_class(i, count), ACTIVE_SEGMENT_CLASS)
+                                    attrs=attrs
+                                >
+                                    {segment.label}
+                                </Button>
+                            }
+                        }
+                    })
+                    .collect_fragment_async()
+                    .await
+            }
         </HtmlElement>
     }
 }
+
+/**
+ * SplitButton
+ *
+ * `PrimaryButton` with an attached `PopupMenu` of secondary actions. The
+ * htmx attributes go to the primary action.
+ */
+#[html_element]
+pub struct SplitButtonProps {
+    children: String,
+
+    // Id of the `PopupMenu`.
+    #[builder(setter(into))]
+    menu_id: String,
+
+    entries: Vec<MenuEntry>,
+
+    #[builder(default = ButtonSize::Md)]
+    size: ButtonSize,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
+}
+
+#[component]
+pub fn SplitButton(props: SplitButtonProps) -> String {
+    let menu_button_class = format!(
+        "inline-flex items-center {} {} {}",
+        props.size.class(true),
+        ButtonVariant::Primary.class(),
+        segment_class(1, 2),
+    );
+
+    html! {
+        <div class=format!("inline-flex rounded-md shadow-sm {}", props.class).trim()>
+            <PrimaryButton
+                size=props.size.clone()
+                confirm=props.confirm.clone()
+                class=segment_class(0, 2)
+                attrs=spread_attrs!(props | omit(class))
+            >
+                {props.children}
+            </PrimaryButton>
+            <PopupMenu
+                id=props.menu_id.clone()
+                class="border-l border-indigo-400"
+                button_class=menu_button_class
+                button_content=html! {
+                    <svg class="h-5 w-5" viewBox="0 0 20 20" fill="currentColor" aria-hidden="true">
+                        <path fill-rule="evenodd" d="M5.23 7.21a.75.75 0 011.06.02L10 11.168l3.71-3.938a.75.75 0 111.08 1.04l-4.25 4.5a.75.75 0 01-1.08 0l-4.25-4.5a.75.75 0 01.02-1.06z" clip-rule="evenodd" />
+                    </svg>
+                }
+                align=MenuAlign::Right
+            >
+                <Menu id=props.menu_id.clone() entries=props.entries />
+            </PopupMenu>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::unescape;
+
+    #[tokio::test]
+    async fn test_icon_only_disabled_link() {
+        let html = unescape(html! {
+            <Button
+                tag="a"
+                href="/settings"
+                variant=ButtonVariant::Ghost
+                leading_icon="<svg></svg>"
+                icon_label="Settings"
+                disabled=true
+            />
+        });
+
+        assert!(html.contains("<svg></svg><span class=\"sr-only\">Settings</span>"));
+        assert!(html.contains("aria-disabled=\"true\""));
+        assert!(!html.contains("href"));
+        assert!(!html.contains("type="));
+        assert!(html.contains("p-1.5"));
+    }
+
+    #[tokio::test]
+    async fn test_loading_button() {
+        let html = html! {
+            <Button kind="submit" variant=ButtonVariant::Danger loading=true>Delete</Button>
+        };
+
+        assert!(html.contains("type=\"submit\""));
+        assert!(html.contains("hx-disabled-elt=\"this\""));
+        assert!(html.contains("data-loading-disable"));
+        assert!(html.contains("data-loading"));
+        assert!(html.contains("bg-red-600"));
+    }
+
+    #[tokio::test]
+    async fn test_button_group_segments() {
+        let html = unescape(html! {
+            <ButtonGroup
+                label="Range"
+                segments=vec![
+                    ButtonSegment::builder().label("Day").hx_get("/day").active(true).build(),
+                    ("Week".to_string(), "/week".to_string()).into(),
+                    ("Month".to_string(), "/month".to_string()).into(),
+                ]
+            />
+        });
+
+        let buttons: Vec<&str> = html.split("<button").skip(1).collect();
+        assert_eq!(buttons.len(), 3);
+        assert!(buttons[0].contains("aria-pressed=\"true\""));
+        assert!(buttons[1].contains("aria-pressed=\"false\""));
+        assert!(buttons[2].contains("hx-get=\"/month\""));
+        assert!(html.contains("aria-label=\"Range\""));
+
+        // (left, right) corners still rounded.
+        assert_eq!(rounded_sides(buttons[0]), (true, false));
+        assert_eq!(rounded_sides(buttons[1]), (false, false));
+        assert_eq!(rounded_sides(buttons[2]), (false, true));
+    }
+
+    #[test]
+    fn test_single_segment_keeps_its_corners() {
+        let class = format!("{} {}", ButtonSize::Md.class(false), segment_class(0, 1));
+        assert_eq!(rounded_sides(&format!("class=\"{}\"", class)), (true, true));
+    }
+
+    // Which sides of the first class attribute in `html` end up rounded, as
+    // Tailwind's stylesheet order decides between conflicting classes: side
+    // utilities (`rounded-l-*`...) after those of all corners, and within each,
+    // by the order of the radius scale ("none" first), not by attribute order.
+    fn rounded_sides(html: &str) -> (bool, bool) {
+        const SCALE: [&str; 9] = [
+            "-none", "-sm", "", "-md", "-lg", "-xl", "-2xl", "-3xl", "-full",
+        ];
+        let class = html
+            .split("class=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+
+        // Radius suffix of the class winning among those with `prefix`.
+        let winner = |prefix: &str| {
+            class
+                .split_whitespace()
+                .filter_map(|class| class.strip_prefix(prefix))
+                .filter_map(|radius| SCALE.iter().position(|scale| *scale == radius))
+                .max()
+                .map(|position| SCALE[position] != "-none")
+        };
+
+        let all = winner("rounded").unwrap_or(false);
+        (
+            winner("rounded-l").unwrap_or(all),
+            winner("rounded-r").unwrap_or(all),
+        )
+    }
+}
@@ -0,0 +1,254 @@
+use rscx::html;
+
+use super::attrs::Attrs;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ConfirmVariant {
+    #[default]
+    Danger,
+    Warning,
+    Neutral,
+}
+
+impl ConfirmVariant {
+    pub const ALL: [ConfirmVariant; 3] = [
+        ConfirmVariant::Danger,
+        ConfirmVariant::Warning,
+        ConfirmVariant::Neutral,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfirmVariant::Danger => "danger",
+            ConfirmVariant::Warning => "warning",
+            ConfirmVariant::Neutral => "neutral",
+        }
+    }
+
+    pub(crate) fn button_class(&self) -> &'static str {
+        match self {
+            ConfirmVariant::Danger => "inline-flex w-full justify-center rounded-md bg-red-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-red-500 disabled:cursor-not-allowed disabled:opacity-50 sm:w-auto",
+            ConfirmVariant::Warning => "inline-flex w-full justify-center rounded-md bg-yellow-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-yellow-500 disabled:cursor-not-allowed disabled:opacity-50 sm:w-auto",
+            ConfirmVariant::Neutral => "inline-flex w-full justify-center rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 disabled:cursor-not-allowed disabled:opacity-50 sm:w-auto",
+        }
+    }
+
+    pub(crate) fn icon(&self) -> String {
+        let (class, path) = match self {
+            ConfirmVariant::Danger => (
+                "text-red-600",
+                "M12 9v3.75m-9.303 3.376c-.866 1.5.217 3.374 1.948 3.374h14.71c1.73 0 2.813-1.874 1.948-3.374L13.949 3.378c-.866-1.5-3.032-1.5-3.898 0L2.697 16.126zM12 15.75h.007v.008H12v-.008z",
+            ),
+            ConfirmVariant::Warning => (
+                "text-yellow-600",
+                "M12 9v3.75m9-.75a9 9 0 11-18 0 9 9 0 0118 0zm-9 3.75h.008v.008H12v-.008z",
+            ),
+            ConfirmVariant::Neutral => (
+                "text-indigo-600",
+                "M9.879 7.519c1.171-1.025 3.071-1.025 4.242 0 1.172 1.025 1.172 2.687 0 3.712-.203.179-.43.326-.67.442-.745.361-1.45.999-1.45 1.827v.75M21 12a9 9 0 11-18 0 9 9 0 0118 0zm-9 5.25h.008v.008H12v-.008z",
+            ),
+        };
+
+        html! {
+            <svg class=format!("h-6 w-6 {}", class) fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                <path stroke-linecap="round" stroke-linejoin="round" d=path />
+            </svg>
+        }
+    }
+
+    pub(crate) fn icon_wrapper_class(&self) -> &'static str {
+        match self {
+            ConfirmVariant::Danger => "bg-red-100",
+            ConfirmVariant::Warning => "bg-yellow-100",
+            ConfirmVariant::Neutral => "bg-indigo-100",
+        }
+    }
+}
+
+/**
+ * Confirm
+ *
+ * Confirmation dialog shown before an `hx-confirm` request is issued.
+ * `add_to` sets it on an element as `hx-confirm` and `data-confirm-*`
+ * attributes, which `CONFIRM_SCRIPT` applies to the `ConfirmDeleteModal`.
+ * Labels left unset fall back to "Delete" and "Cancel".
+ * Build it with `Confirm::new` and the option setters, fields may be added.
+ */
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Confirm {
+    pub title: String,
+    pub message: String,
+    pub confirm_label: Option<String>,
+    pub cancel_label: Option<String>,
+    pub variant: ConfirmVariant,
+    // Text the user has to type before confirming, e.g. the name of what gets deleted.
+    pub confirm_text: Option<String>,
+}
+
+impl Confirm {
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            confirm_label: None,
+            cancel_label: None,
+            variant: ConfirmVariant::default(),
+            confirm_text: None,
+        }
+    }
+
+    pub fn confirm_label(mut self, label: impl Into<String>) -> Self {
+        self.confirm_label = Some(label.into());
+        self
+    }
+
+    pub fn cancel_label(mut self, label: impl Into<String>) -> Self {
+        self.cancel_label = Some(label.into());
+        self
+    }
+
+    pub fn variant(mut self, variant: ConfirmVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn type_to_confirm(mut self, text: impl Into<String>) -> Self {
+        self.confirm_text = Some(text.into());
+        self
+    }
+
+    pub fn add_to(&self, attrs: &Attrs) -> Attrs {
+        let mut attrs = attrs
+            .set("hx-confirm", self.title.clone())
+            .set("data-confirm-message", self.message.clone())
+            .set("data-confirm-variant", self.variant.as_str().into());
+
+        if let Some(label) = &self.confirm_label {
+            attrs = attrs.set("data-confirm-label", label.clone());
+        }
+        if let Some(label) = &self.cancel_label {
+            attrs = attrs.set("data-confirm-cancel-label", label.clone());
+        }
+        if let Some(text) = &self.confirm_text {
+            attrs = attrs.set("data-confirm-text", text.clone());
+        }
+
+        attrs
+    }
+
+    pub fn to_attrs(&self) -> Attrs {
+        self.add_to(&Attrs::default())
+    }
+}
+
+// Adds the confirm attributes, if any, e.g. for components with a `confirm: Option<Confirm>` prop.
+pub fn with_confirm(attrs: Attrs, confirm: &Option<Confirm>) -> Attrs {
+    match confirm {
+        Some(confirm) => confirm.add_to(&attrs),
+        None => attrs,
+    }
+}
+
+// Shows the `ConfirmDeleteModal` for `hx-confirm` elements, set up from their `data-confirm-*` attributes.
+// `hx-confirm` may be inherited from an ancestor, which then holds the `data-confirm-*` attributes too.
+// Requests another `htmx:confirm` listener already handled are left alone, and once handled here,
+// listeners added later (e.g. the one previously copied from the README) don't show a second dialog.
+pub(crate) const CONFIRM_SCRIPT: &str = r#"
+(function() {
+    function prepareConfirm(content, data) {
+        var variant = data.confirmVariant || 'danger';
+        content.querySelectorAll('[data-confirm-icon]').forEach(function(icon) {
+            icon.classList.toggle('hidden', icon.getAttribute('data-confirm-icon') !== variant);
+        });
+
+        var action = content.querySelector('[data-confirm-action]');
+        action.textContent = data.confirmLabel || action.getAttribute('data-confirm-default-label');
+        action.className = action.getAttribute('data-confirm-class-' + variant);
+        action.disabled = !!data.confirmText;
+
+        var cancel = content.querySelector('[data-confirm-cancel]');
+        cancel.textContent = data.confirmCancelLabel || cancel.getAttribute('data-confirm-default-label');
+
+        var group = content.querySelector('[data-confirm-input-group]');
+        group.classList.toggle('hidden', !data.confirmText);
+        group.querySelector('[data-confirm-text]').textContent = data.confirmText || '';
+        group.querySelector('[data-confirm-input]').setAttribute('data-confirm-expected', data.confirmText || '');
+    }
+
+    document.addEventListener('htmx:confirm', function(event) {
+        if (event.defaultPrevented) return;
+        var el = event.target.closest('[hx-confirm], [data-hx-confirm]');
+        if (!el) return;
+        event.preventDefault();
+        event.stopImmediatePropagation();
+
+        var template = document.getElementById('tpl-confirm-delete-modal');
+        if (template) prepareConfirm(template.content, el.dataset);
+
+        YcControls.confirm({
+            title: event.detail.question || el.getAttribute('hx-confirm') || el.getAttribute('data-hx-confirm'),
+            message: el.dataset.confirmMessage,
+            actionConfirmed: function() {
+                event.detail.issueRequest(true);
+            }
+        });
+    });
+
+    document.addEventListener('input', function(event) {
+        var input = event.target;
+        if (!input.matches('[data-confirm-input]')) return;
+        var modal = input.closest('[data-yc-control="modal"]');
+        var action = modal && modal.querySelector('[data-confirm-action]');
+        if (action) action.disabled = input.value !== input.getAttribute('data-confirm-expected');
+    });
+})();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_attrs_with_defaults() {
+        let attrs = Confirm::new("Delete user?", "This can not be undone.").to_attrs();
+
+        assert_eq!(attrs.get("hx-confirm").unwrap(), "Delete user?");
+        assert_eq!(
+            attrs.get("data-confirm-message").unwrap(),
+            "This can not be undone."
+        );
+        assert_eq!(attrs.get("data-confirm-variant").unwrap(), "danger");
+        assert_eq!(attrs.get("data-confirm-label"), None);
+        assert_eq!(attrs.get("data-confirm-text"), None);
+    }
+
+    #[test]
+    fn test_to_attrs_with_options() {
+        let attrs = Confirm::new("Archive project?", "It can be restored later.")
+            .variant(ConfirmVariant::Warning)
+            .confirm_label("Archive")
+            .cancel_label("Keep")
+            .type_to_confirm("apollo")
+            .to_attrs();
+
+        assert_eq!(attrs.get("data-confirm-variant").unwrap(), "warning");
+        assert_eq!(attrs.get("data-confirm-label").unwrap(), "Archive");
+        assert_eq!(attrs.get("data-confirm-cancel-label").unwrap(), "Keep");
+        assert_eq!(attrs.get("data-confirm-text").unwrap(), "apollo");
+    }
+
+    #[tokio::test]
+    async fn test_button_with_confirm() {
+        use crate::server::button::PrimaryButton;
+
+        let html = html! {
+            <PrimaryButton confirm=Some(Confirm::new("Publish?", "Everyone will see it.").variant(ConfirmVariant::Neutral))>
+                Publish
+            </PrimaryButton>
+        };
+
+        assert!(html.contains("hx-confirm=\"Publish?\""));
+        assert!(html.contains("data-confirm-variant=\"neutral\""));
+    }
+}
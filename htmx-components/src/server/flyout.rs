@@ -1,58 +1,152 @@
 use super::attrs::Attrs;
-use super::transition::Transition;
+use super::transition::{Transition, TransitionDuration, TransitionPreset};
 use super::yc_control::YcControl;
+use crate::unique_id;
 use rscx::{component, html, props};
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum FlyoutSide {
+    Left,
+    Right,
+}
+
+pub enum FlyoutSize {
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+    Custom(String),
+}
+
+/**
+ * Flyout
+ *
+ * A panel sliding in from the `side` of the screen. `footer` holds actions
+ * that stay visible while the content scrolls. With `overlay`, the page
+ * behind the panel is dimmed and the flyout is modal. Without it, the page
+ * stays usable next to the panel.
+ */
 #[props]
 pub struct FlyoutProps {
     #[builder(setter(into))]
     title: String,
+
+    #[builder(setter(into), default)]
+    description: String,
+
+    #[builder(default = FlyoutSide::Right)]
+    side: FlyoutSide,
+
+    #[builder(default = FlyoutSize::Large)]
+    size: FlyoutSize,
+
+    #[builder(setter(into), default)]
+    footer: String,
+
+    #[builder(default = false)]
+    overlay: bool,
+
     children: String,
 }
 
 #[component]
 pub fn Flyout(props: FlyoutProps) -> String {
+    let title_id = unique_id("flyout-title");
+    let description_id = unique_id("flyout-description");
+    let has_description = !props.description.is_empty();
+
+    let (container_class, preset) = match props.side {
+        FlyoutSide::Left => ("left-0 pr-10 sm:pr-16", TransitionPreset::SlideFromLeft),
+        FlyoutSide::Right => ("right-0 pl-10 sm:pl-16", TransitionPreset::SlideFromRight),
+    };
+    // Without an overlay, only the panel catches pointer events.
+    let wrapper_class = if props.overlay {
+        "fixed inset-0 overflow-hidden"
+    } else {
+        "pointer-events-none fixed inset-0 overflow-hidden"
+    };
+    let width = match props.size {
+        FlyoutSize::Small => "max-w-md".to_string(),
+        FlyoutSize::Medium => "max-w-xl".to_string(),
+        FlyoutSize::Large => "max-w-2xl".to_string(),
+        FlyoutSize::ExtraLarge => "max-w-4xl".to_string(),
+        FlyoutSize::Custom(width) => width,
+    };
+
     html! {
         <YcControl
             control="flyout"
             class="relative z-8"
             role="dialog"
-            aria_labelledby="slide-over-title"
-            attrs=Attrs::with("aria-modal", "true".into())
+            aria_labelledby=title_id.clone()
+            attrs=Attrs::default()
+                .set_if("aria-modal", "true".into(), props.overlay)
+                .set_if("aria-describedby", description_id.clone(), has_description)
         >
-            // <!-- Background backdrop, show/hide based on slide-over state. -->
-            <div class="fixed inset-0"></div>
-            <div class="fixed inset-0 overflow-hidden">
+            {
+                if props.overlay {
+                    html! {
+                        <Transition
+                            class="fixed inset-0 bg-gray-500 bg-opacity-75"
+                            preset=TransitionPreset::Fade
+                            duration=TransitionDuration::Slow
+                        />
+                    }
+                } else {
+                    html! {
+                        <div class="pointer-events-none fixed inset-0"></div>
+                    }
+                }
+            }
+            <div class=wrapper_class>
                 <div class="absolute inset-0 overflow-hidden">
-                    <div class="pointer-events-none fixed inset-y-0 right-0 flex max-w-full pl-10 sm:pl-16">
+                    <div class=format!("pointer-events-none fixed inset-y-0 flex max-w-full {}", container_class)>
                         <Transition
-                            class="pointer-events-auto w-screen max-w-2xl"
-                            enter="transform transition ease-in-out duration-500 sm:duration-700"
-                            enter_from="translate-x-full"
-                            enter_to="translate-x-0"
-                            leave="transform transition ease-in-out duration-500 sm:duration-700"
-                            leave_from="translate-x-0"
-                            leave_to="translate-x-full"
+                            class=format!("pointer-events-auto w-screen {}", width)
+                            preset=preset
+                            duration=TransitionDuration::Slow
                             attrs=Attrs::with("data-flyout-panel", "true".into())
                         >
-                            <div class="flex h-full flex-col overflow-y-scroll bg-white py-6 shadow-xl">
-                                <div class="px-4 sm:px-6">
-                                    <div class="flex items-start justify-between">
-                                        <h2 class="text-base font-semibold leading-6 text-gray-900" id="slide-over-title">{props.title}</h2>
-                                        <div class="ml-3 flex h-7 items-center">
-                                            <button type="button" data-toggle-action="close" class="relative rounded-md bg-white text-gray-400 hover:text-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2">
-                                                <span class="absolute -inset-2.5"></span>
-                                                <span class="sr-only">Close panel</span>
-                                                <svg class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
-                                                    <path stroke-linecap="round" stroke-linejoin="round" d="M6 18L18 6M6 6l12 12" />
-                                                </svg>
-                                            </button>
+                            <div class="flex h-full flex-col bg-white shadow-xl">
+                                <div class="flex min-h-0 flex-1 flex-col overflow-y-scroll py-6">
+                                    <div class="px-4 sm:px-6">
+                                        <div class="flex items-start justify-between">
+                                            <h2 class="text-base font-semibold leading-6 text-gray-900" id=title_id>{props.title}</h2>
+                                            <div class="ml-3 flex h-7 items-center">
+                                                <button type="button" data-toggle-action="close" class="relative rounded-md bg-white text-gray-400 hover:text-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2">
+                                                    <span class="absolute -inset-2.5"></span>
+                                                    <span class="sr-only">Close panel</span>
+                                                    <svg class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                                                        <path stroke-linecap="round" stroke-linejoin="round" d="M6 18L18 6M6 6l12 12" />
+                                                    </svg>
+                                                </button>
+                                            </div>
                                         </div>
+                                        {
+                                            if has_description {
+                                                html! {
+                                                    <p class="mt-1 text-sm text-gray-500" id=description_id>{props.description}</p>
+                                                }
+                                            } else {
+                                                String::new()
+                                            }
+                                        }
+                                    </div>
+                                    <div class="relative mt-6 flex-1 px-4 sm:px-6">
+                                        {props.children}
                                     </div>
                                 </div>
-                                <div class="relative mt-6 flex-1 px-4 sm:px-6">
-                                    {props.children}
-                                </div>
+                                {
+                                    if props.footer.is_empty() {
+                                        String::new()
+                                    } else {
+                                        html! {
+                                            <div class="flex flex-shrink-0 justify-end gap-3 border-t border-gray-200 px-4 py-4 sm:px-6">
+                                                {props.footer}
+                                            </div>
+                                        }
+                                    }
+                                }
                             </div>
                         </Transition>
                     </div>
@@ -61,3 +155,78 @@ pub fn Flyout(props: FlyoutProps) -> String {
         </YcControl>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::unescape;
+
+    fn labelledby(html: &str) -> String {
+        html.split("aria-labelledby=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_each_flyout_gets_its_own_title_id() {
+        let first = html! { <Flyout title="One">Content</Flyout> };
+        let second = html! { <Flyout title="Two" side=FlyoutSide::Left>Content</Flyout> };
+
+        assert_ne!(labelledby(&first), labelledby(&second));
+        assert!(second.contains("-translate-x-full"));
+    }
+
+    #[tokio::test]
+    async fn test_side_and_size() {
+        let html = unescape(html! { <Flyout title="Right">Content</Flyout> });
+        assert!(html.contains("right-0 pl-10 sm:pl-16"));
+        assert!(html.contains("w-screen max-w-2xl"));
+
+        let html = unescape(html! {
+            <Flyout title="Left" side=FlyoutSide::Left size=FlyoutSize::Small>Content</Flyout>
+        });
+        assert!(html.contains("left-0 pr-10 sm:pr-16"));
+        assert!(html.contains("w-screen max-w-md"));
+
+        let html = unescape(html! {
+            <Flyout title="Custom" size=FlyoutSize::Custom("max-w-7xl".into())>Content</Flyout>
+        });
+        assert!(html.contains("w-screen max-w-7xl"));
+    }
+
+    #[tokio::test]
+    async fn test_description_and_footer() {
+        let html = unescape(html! { <Flyout title="Plain">Content</Flyout> });
+        assert!(!html.contains("aria-describedby"));
+        assert!(!html.contains("border-t border-gray-200"));
+
+        let html = unescape(html! {
+            <Flyout title="Edit" description="Changes are saved." footer="<button>Save</button>">
+                Content
+            </Flyout>
+        });
+        let description_id = html
+            .split("aria-describedby=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+        assert!(html.contains(&format!("id=\"{}\">Changes are saved.</p>", description_id)));
+        assert!(html.contains("border-t border-gray-200 px-4 py-4 sm:px-6\"><button>Save</button>"));
+    }
+
+    #[tokio::test]
+    async fn test_overlay() {
+        let html = unescape(html! { <Flyout title="Modal" overlay=true>Content</Flyout> });
+        assert!(html.contains("aria-modal=\"true\""));
+        assert!(html.contains("bg-gray-500 bg-opacity-75"));
+        assert!(html.contains("class=\"fixed inset-0 overflow-hidden\""));
+
+        let html = unescape(html! { <Flyout title="Panel">Content</Flyout> });
+        assert!(!html.contains("aria-modal"));
+        assert!(!html.contains("bg-gray-500"));
+        assert!(html.contains("class=\"pointer-events-none fixed inset-0 overflow-hidden\""));
+        assert!(html.contains("pointer-events-auto w-screen"));
+    }
+}
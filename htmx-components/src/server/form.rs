@@ -2,7 +2,12 @@ use rscx::{component, html, props};
 
 use rscx_web_macros::*;
 
-use super::{attrs::Attrs, html_element::HtmlElement};
+use super::{
+    attrs::Attrs,
+    button::{Button as StyledButton, ButtonSize, ButtonVariant},
+    confirm::Confirm,
+    html_element::HtmlElement,
+};
 use crate::server::yc_control::YcControl;
 
 #[rscx_web_macros::html_element]
@@ -137,24 +142,30 @@ pub struct ButtonProps {
     #[builder(setter(into), default="button".into())]
     kind: String,
     children: String,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
 }
 
+// Primary submit button, other kinds are text only. See `button::Button` for more options.
 #[component]
 pub fn Button(props: ButtonProps) -> String {
-    let button_type = props.kind.clone();
-    let css = match props.kind.as_str() {
-        "submit" => "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
-        _ => "text-sm font-semibold leading-6 text-gray-900",
+    let variant = match props.kind.as_str() {
+        "submit" => ButtonVariant::Primary,
+        _ => ButtonVariant::Link,
     };
 
     html! {
-        <HtmlElement
-            tag="button"
-            class=format!("{} {}", css, props.class).trim()
-            attrs=spread_attrs!(props | omit(class, name)).set("type", button_type)
+        <StyledButton
+            variant=variant
+            size=ButtonSize::Lg
+            kind=props.kind.clone()
+            confirm=props.confirm.clone()
+            class=props.class.clone()
+            attrs=spread_attrs!(props | omit(class, name))
         >
             {props.children}
-        </HtmlElement>
+        </StyledButton>
     }
 }
 
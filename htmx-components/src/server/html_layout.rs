@@ -2,8 +2,47 @@ use once_cell::sync::Lazy;
 use rscx::{component, html, props};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::button::BUTTON_GROUP_SCRIPT;
+use super::confirm::CONFIRM_SCRIPT;
+use super::modal::{route::MODAL_ROUTE_SCRIPT, MODAL_SCRIPT};
+use super::notification::NOTIFICATION_SCRIPT;
+use super::popup_menu::POPUP_MENU_SCRIPT;
+use super::table::BULK_SELECT_SCRIPT;
+
+// Client-side behavior of components not (yet) provided by htmx-glue.
+const COMPONENT_SCRIPTS: [&str; 7] = [
+    BULK_SELECT_SCRIPT,
+    MODAL_SCRIPT,
+    MODAL_ROUTE_SCRIPT,
+    CONFIRM_SCRIPT,
+    NOTIFICATION_SCRIPT,
+    POPUP_MENU_SCRIPT,
+    BUTTON_GROUP_SCRIPT,
+];
+
+// Queues htmx-glue controls until its script is loaded at the end of the body.
+const YC_CONTROLS_SCRIPT: &str = "window.YcControls = {
+    attachOnReadyQueue: [],
+    attach: function(element) {
+        this.attachOnReadyQueue.push(element);
+    },
+    onReadyQueue: [],
+    onReady: function(onReadyHandler) {
+        this.onReadyQueue.push(onReadyHandler);
+    },
+};";
+
+fn inline_script(nonce: &str, script: &str) -> String {
+    if nonce.is_empty() {
+        html! { <script>{script}</script> }
+    } else {
+        html! { <script nonce=nonce>{script}</script> }
+    }
+}
+
 // TEMP HACK! Used to bust cache on client scripts and stylesheets.
 // TODO Get hash of each build file and use that.
+#[allow(dead_code)]
 static TS: Lazy<u128> = Lazy::new(|| {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -22,6 +61,10 @@ pub struct HtmlLayoutProps {
     #[builder(default)]
     head_scripts: String,
 
+    // CSP nonce, see `HtmlLayout`.
+    #[builder(setter(into), default)]
+    nonce: String,
+
     #[builder(default)]
     children: String,
 }
@@ -31,9 +74,21 @@ pub struct HtmlLayoutProps {
 * theres a bunch to figure out how to get this working well as a library, what with all
 * the tailwind building and themes and junk.
 *
+* The component scripts are inline. To serve the page with a strict
+* `Content-Security-Policy` (`script-src 'nonce-...'`), pass the nonce of the
+* response as `nonce`: the inline scripts get it, and htmx adds it to the
+* scripts it swaps in (e.g. from `NotificationPresenter`). Scripts in
+* `head_scripts` need it as well.
 */
 #[component]
 pub fn HtmlLayout(props: HtmlLayoutProps) -> String {
+    let htmx_config = if props.nonce.is_empty() {
+        String::new()
+    } else {
+        let config = serde_json::json!({ "inlineScriptNonce": props.nonce }).to_string();
+        html! { <meta name="htmx-config" content=config /> }
+    };
+
     html! {
         <!DOCTYPE html>
         <html lang="en">
@@ -42,18 +97,9 @@ pub fn HtmlLayout(props: HtmlLayoutProps) -> String {
                 <meta name="viewport" content="width=device-width, initial-scale=1" />
                 <title>{props.head_title}</title>
                 <script src="https://cdn.tailwindcss.com"></script>
-                <script>{
-                    "window.YcControls = {
-                        attachOnReadyQueue: [],
-                        attach: function(element) {
-                            this.attachOnReadyQueue.push(element);
-                        },
-                        onReadyQueue: [],
-                        onReady: function(onReadyHandler) {
-                            this.onReadyQueue.push(onReadyHandler);
-                        },
-                    };"
-                }</script>
+                {htmx_config}
+                {inline_script(&props.nonce, YC_CONTROLS_SCRIPT)}
+                {inline_script(&props.nonce, &COMPONENT_SCRIPTS.concat())}
                 {props.head_links}
                 {props.head_scripts}
             </head>
@@ -64,3 +110,28 @@ pub fn HtmlLayout(props: HtmlLayoutProps) -> String {
         </html>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::unescape;
+
+    #[tokio::test]
+    async fn test_without_nonce() {
+        let html = html! { <HtmlLayout>Page</HtmlLayout> };
+
+        assert!(!html.contains("nonce"));
+        assert!(html.contains("<script>window.YcControls = {"));
+    }
+
+    #[tokio::test]
+    async fn test_nonce_is_set_on_inline_scripts() {
+        let html = unescape(html! { <HtmlLayout nonce="r4nd0m">Page</HtmlLayout> });
+
+        assert_eq!(html.matches("<script nonce=\"r4nd0m\">").count(), 2);
+        assert_eq!(html.matches("<script>").count(), 0);
+        assert!(
+            html.contains(r#"<meta name="htmx-config" content="{"inlineScriptNonce":"r4nd0m"}""#)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::convert::Infallible;
+
+use axum::{
+    http::HeaderValue,
+    response::{IntoResponseParts, ResponseParts},
+};
+use serde_json::{Map, Value};
+
+pub const HX_TRIGGER: &str = "HX-Trigger";
+
+/**
+ * HxTrigger
+ *
+ * Client-side events to trigger with the `HX-Trigger` response header.
+ * Use it as a response part, e.g. `(HxTrigger::new().event("saved"), Html(..))`.
+ * Events already set on the response by other parts are kept.
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HxTrigger {
+    events: Map<String, Value>,
+}
+
+impl HxTrigger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn event(self, name: impl Into<String>) -> Self {
+        self.event_with(name, Value::Null)
+    }
+
+    // `detail` ends up as `event.detail` (or `event.detail.value` for non-objects).
+    pub fn event_with(mut self, name: impl Into<String>, detail: Value) -> Self {
+        self.events.insert(name.into(), detail);
+        self
+    }
+
+    pub fn merge(mut self, other: HxTrigger) -> Self {
+        self.events.extend(other.events);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.events.get(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    // Reads a `HX-Trigger` header value, either JSON or a comma separated list of event names.
+    pub fn parse(value: &str) -> Self {
+        match serde_json::from_str::<Value>(value) {
+            Ok(Value::Object(events)) => Self { events },
+            Ok(Value::String(name)) => Self::new().event(name),
+            _ => value
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .fold(Self::new(), |trigger, name| trigger.event(name)),
+        }
+    }
+
+    // Non-ASCII characters are escaped, browsers don't read header values as UTF-8.
+    pub fn to_header_value(&self) -> String {
+        Value::Object(self.events.clone()).to_string().chars().fold(
+            String::new(),
+            |mut value, c| {
+                if c.is_ascii() {
+                    value.push(c);
+                } else {
+                    for unit in c.encode_utf16(&mut [0; 2]) {
+                        value.push_str(&format!("\\u{:04x}", unit));
+                    }
+                }
+                value
+            },
+        )
+    }
+}
+
+impl IntoResponseParts for HxTrigger {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if self.is_empty() {
+            return Ok(res);
+        }
+
+        let trigger = match res
+            .headers()
+            .get(HX_TRIGGER)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(existing) => HxTrigger::parse(existing).merge(self),
+            None => self,
+        };
+
+        if let Ok(value) = HeaderValue::from_str(&trigger.to_header_value()) {
+            res.headers_mut().insert(HX_TRIGGER, value);
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_header_value() {
+        let trigger = HxTrigger::new()
+            .event("saved")
+            .event_with("refresh", json!({ "list": "users" }));
+
+        assert_eq!(
+            trigger.to_header_value(),
+            r#"{"refresh":{"list":"users"},"saved":null}"#
+        );
+    }
+
+    #[test]
+    fn test_to_header_value_escapes_non_ascii() {
+        let trigger = HxTrigger::new().event_with("notify", json!({ "message": "50%…" }));
+
+        assert_eq!(
+            trigger.to_header_value(),
+            r#"{"notify":{"message":"50%\u2026"}}"#
+        );
+        assert_eq!(HxTrigger::parse(&trigger.to_header_value()), trigger);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            HxTrigger::parse("saved, refresh"),
+            HxTrigger::new().event("saved").event("refresh")
+        );
+        assert_eq!(
+            HxTrigger::parse(r#"{"saved":{"id":1}}"#),
+            HxTrigger::new().event_with("saved", json!({ "id": 1 }))
+        );
+    }
+
+    #[test]
+    fn test_response_parts_merge() {
+        let response = (
+            HxTrigger::new().event("saved"),
+            HxTrigger::new().event("closed"),
+            "body",
+        )
+            .into_response();
+
+        assert_eq!(
+            response.headers().get(HX_TRIGGER).unwrap(),
+            r#"{"closed":null,"saved":null}"#
+        );
+    }
+}
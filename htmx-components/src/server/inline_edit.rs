@@ -0,0 +1,253 @@
+use std::fmt::Display;
+
+use rscx::{html, html_escape, props::Props, CollectFragmentAsync};
+use typed_builder::TypedBuilder;
+
+use super::attrs::Attrs;
+use super::form::{Button, Select, SelectOption, TextInput};
+
+// NOTE: rscx's #[props] and #[component] macros don't support generics, so the
+// components below derive their builders and implement `Props` by hand.
+
+pub enum InlineEditInput<T> {
+    Text,
+    Textarea,
+    Select(Vec<T>),
+}
+
+/**
+ * InlineEdit
+ *
+ * Read view of a click-to-edit field. The edit trigger swaps this element
+ * with the `InlineEditForm` returned from `edit_url`.
+ */
+
+#[derive(TypedBuilder)]
+#[builder(doc, crate_module_path=::rscx::typed_builder)]
+pub struct InlineEditProps<T: Display> {
+    #[builder(setter(into))]
+    id: String,
+
+    value: T,
+
+    #[builder(setter(into))]
+    edit_url: String,
+
+    #[builder(setter(into), default = "Edit".into())]
+    sr_text: String,
+
+    #[builder(setter(into), default)]
+    class: String,
+}
+
+impl<T: Display> Props for InlineEditProps<T> {
+    type Builder = InlineEditPropsBuilder<T>;
+    fn builder() -> Self::Builder {
+        InlineEditProps::builder()
+    }
+}
+
+#[allow(non_snake_case)]
+pub async fn InlineEdit<T: Display>(props: InlineEditProps<T>) -> String {
+    html! {
+        <div
+            id=props.id.clone()
+            class=format!("group inline-flex items-center gap-2 {}", props.class).trim()
+            data-inline-edit="read"
+        >
+            <span>{html_escape::encode_text(&props.value.to_string())}</span>
+            <button
+                type="button"
+                class="rounded-md text-gray-400 opacity-0 hover:text-indigo-600 focus:opacity-100 focus:outline-none focus:ring-2 focus:ring-indigo-500 group-hover:opacity-100"
+                hx-get=props.edit_url
+                hx-target=format!("#{}", props.id)
+                hx-swap="outerHTML"
+            >
+                <svg class="h-4 w-4" viewBox="0 0 20 20" fill="currentColor" aria-hidden="true">
+                    <path d="M2.695 14.763l-1.262 3.154a.5.5 0 00.65.65l3.155-1.262a4 4 0 001.343-.885L17.5 5.5a2.121 2.121 0 00-3-3L3.58 13.42a4 4 0 00-.885 1.343z" />
+                </svg>
+                <span class="sr-only">{props.sr_text}</span>
+            </button>
+        </div>
+    }
+}
+
+/**
+ * InlineEditForm
+ *
+ * Edit view of a click-to-edit field. Saving `hx-put`s to `save_url`, which
+ * should respond with the `InlineEdit` read view on success, or this form
+ * again (with `error` set) to show validation errors in place.
+ * Cancelling swaps back to the read view returned from `cancel_url`.
+ */
+
+#[derive(TypedBuilder)]
+#[builder(doc, crate_module_path=::rscx::typed_builder)]
+pub struct InlineEditFormProps<T: Display + PartialEq> {
+    #[builder(setter(into))]
+    id: String,
+
+    #[builder(setter(into))]
+    name: String,
+
+    value: T,
+
+    #[builder(default = InlineEditInput::Text)]
+    input: InlineEditInput<T>,
+
+    #[builder(setter(into))]
+    save_url: String,
+
+    #[builder(setter(into))]
+    cancel_url: String,
+
+    #[builder(setter(into), default=None)]
+    error: Option<String>,
+
+    #[builder(setter(into), default)]
+    class: String,
+}
+
+impl<T: Display + PartialEq> Props for InlineEditFormProps<T> {
+    type Builder = InlineEditFormPropsBuilder<T>;
+    fn builder() -> Self::Builder {
+        InlineEditFormProps::builder()
+    }
+}
+
+#[allow(non_snake_case)]
+pub async fn InlineEditForm<T: Display + PartialEq + Sync>(
+    props: InlineEditFormProps<T>,
+) -> String {
+    let value = props.value.to_string();
+
+    html! {
+        <form
+            id=props.id
+            class=format!("flex items-start gap-2 {}", props.class).trim()
+            hx-put=props.save_url
+            hx-target="this"
+            hx-swap="outerHTML"
+            data-inline-edit="edit"
+        >
+            <div class="min-w-0 flex-1">
+                {
+                    match props.input {
+                        InlineEditInput::Text => html! {
+                            <TextInput
+                                name=props.name
+                                value=value
+                                error=props.error
+                                attrs=Attrs::with("autofocus", "true".into())
+                            />
+                        },
+                        InlineEditInput::Textarea => html! {
+                            <TextInput
+                                input_type="textarea"
+                                name=props.name
+                                value=value
+                                error=props.error
+                                attrs=Attrs::with("autofocus", "true".into())
+                            />
+                        },
+                        InlineEditInput::Select(options) => html! {
+                            <Select name=props.name error=props.error>
+                                {
+                                    options
+                                        .iter()
+                                        .map(|option| (option.to_string(), option == &props.value))
+                                        .map(|(option, selected)| async move {
+                                            html! {
+                                                <SelectOption value=option selected=selected>
+                                                    {html_escape::encode_text(&option)}
+                                                </SelectOption>
+                                            }
+                                        })
+                                        .collect_fragment_async()
+                                        .await
+                                }
+                            </Select>
+                        },
+                    }
+                }
+            </div>
+            <Button kind="submit">Save</Button>
+            <Button
+                class="py-2"
+                hx_get=props.cancel_url
+                hx_target="closest form"
+                hx_swap="outerHTML"
+            >
+                Cancel
+            </Button>
+        </form>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::unescape;
+
+    #[tokio::test]
+    async fn test_read_view() {
+        let html = unescape(html! {
+            <InlineEdit id="user-name" value="Jane" edit_url="/users/1/name/edit" />
+        });
+
+        assert!(html.contains("id=\"user-name\""));
+        assert!(html.contains("data-inline-edit=\"read\""));
+        assert!(html.contains("<span>Jane</span>"));
+        assert!(html.contains("hx-get=\"/users/1/name/edit\""));
+        assert!(html.contains("hx-target=\"#user-name\""));
+        assert!(html.contains("hx-swap=\"outerHTML\""));
+    }
+
+    #[tokio::test]
+    async fn test_read_view_escapes_value() {
+        let html = html! {
+            <InlineEdit id="nickname" value="<script>alert(1)</script>" edit_url="/edit" />
+        };
+
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<span>&lt;script&gt;alert(1)&lt;/script&gt;</span>"));
+    }
+
+    #[tokio::test]
+    async fn test_edit_view() {
+        let html = unescape(html! {
+            <InlineEditForm
+                id="user-name"
+                name="name"
+                value="Jane"
+                save_url="/users/1/name"
+                cancel_url="/users/1/name"
+                error=Some("Name is taken".to_string())
+            />
+        });
+
+        assert!(html.contains("data-inline-edit=\"edit\""));
+        assert!(html.contains("hx-put=\"/users/1/name\""));
+        assert!(html.contains("hx-target=\"this\""));
+        assert!(html.contains("hx-target=\"closest form\""));
+        assert!(html.contains("value=\"Jane\""));
+        assert!(html.contains("Name is taken"));
+    }
+
+    #[tokio::test]
+    async fn test_edit_view_with_select() {
+        let html = html! {
+            <InlineEditForm
+                id="user-role"
+                name="role"
+                value="Admin"
+                input=InlineEditInput::Select(vec!["Admin", "Member"])
+                save_url="/users/1/role"
+                cancel_url="/users/1/role"
+            />
+        };
+
+        assert_eq!(html.matches("<option").count(), 2);
+        assert_eq!(html.matches("selected=\"selected\"").count(), 1);
+    }
+}
@@ -1,13 +1,155 @@
-use super::transition::Transition;
+use super::button::SecondaryButton;
+use super::confirm::ConfirmVariant;
+use super::form::Button;
+use super::html_element::HtmlElement;
+use super::hx_trigger::HxTrigger;
+use super::notification::{NotificationCall, NotificationPresenter};
+use super::transition::{Transition, TransitionPreset};
 use super::yc_control::YcControl;
 use crate::server::attrs::Attrs;
-use rscx::{component, html, props};
+use crate::unique_id;
+use axum::response::{Html, IntoResponse, Response};
+use rscx::{component, html, props, CollectFragment};
+use rscx_web_macros::*;
+use serde_json::json;
+
+pub mod route;
 
 const MODALS_ID: &str = "modal-live-region";
 pub fn modal_target() -> String {
     format!("#{}", MODALS_ID)
 }
 
+pub const MODAL_CLOSE_EVENT: &str = "modal-close";
+
+/**
+ * open_modal
+ *
+ * Responds with a rendered `Modal`, shown in the `ModalLiveRegion` whatever
+ * the target of the request was.
+ */
+pub fn open_modal(modal: String) -> Response {
+    (
+        [
+            (
+                "HX-Retarget",
+                format!("{} [data-modal-content]", modal_target()),
+            ),
+            ("HX-Reswap", "beforeend".to_string()),
+        ],
+        Html(modal),
+    )
+        .into_response()
+}
+
+// Closes the top-most open modal, e.g. `(close_modal(), Html(..))` after a successful save.
+pub fn close_modal() -> HxTrigger {
+    HxTrigger::new().event_with(MODAL_CLOSE_EVENT, json!({ "all": false }))
+}
+
+pub fn close_all_modals() -> HxTrigger {
+    HxTrigger::new().event_with(MODAL_CLOSE_EVENT, json!({ "all": true }))
+}
+
+// Keeps a stack of the open modals: each new modal is raised above the previous one,
+// Escape only closes the top one and focus returns to where it was before opening.
+// Modals are dismissed through their close button, so the modal control runs its leave transition.
+pub(crate) const MODAL_SCRIPT: &str = r#"
+(function() {
+    var MODAL = '[data-yc-control="modal"]';
+    var FOCUSABLE = 'button:not([data-modal-dismiss]), [href], input, select, textarea, [tabindex]:not([tabindex="-1"])';
+    var stack = [];
+    var nextId = 1;
+
+    function isOpen(entry) {
+        return entry.modal.isConnected && !entry.modal.hasAttribute('data-modal-closed');
+    }
+
+    function restoreFocus(entry) {
+        var target = entry.opener && entry.opener.isConnected ? entry.opener : null;
+        if (!target && stack.length) {
+            target = stack[stack.length - 1].modal.querySelector(FOCUSABLE);
+        }
+        if (target && target.focus) target.focus();
+    }
+
+    function prune() {
+        var closed = stack.filter(function(entry) { return !isOpen(entry); });
+        stack = stack.filter(isOpen);
+        closed.forEach(restoreFocus);
+        if (closed.length && !stack.length) {
+            document.dispatchEvent(new CustomEvent('modal-stack-empty'));
+        }
+    }
+
+    // Template clones and nested content can repeat the ids the dialog is labelled by.
+    function dedupeIds(modal) {
+        ['aria-labelledby', 'aria-describedby'].forEach(function(attr) {
+            var id = modal.getAttribute(attr);
+            var el = id && modal.querySelector('[id="' + id + '"]');
+            if (el && document.querySelectorAll('[id="' + id + '"]').length > 1) {
+                el.id = id + '-' + nextId++;
+                modal.setAttribute(attr, el.id);
+            }
+        });
+    }
+
+    function open(modal) {
+        if (stack.some(function(entry) { return entry.modal === modal; })) return;
+        prune();
+        dedupeIds(modal);
+        modal.style.zIndex = 10 * (stack.length + 1);
+        stack.push({ modal: modal, opener: document.activeElement });
+    }
+
+    // An open `PopupMenu` handles Escape itself: it closes and returns focus to its button.
+    function inOpenMenu(target) {
+        var menu = target.closest && target.closest('[data-popup-menu]');
+        return !!(menu && menu.querySelector('[data-menu-button][aria-expanded="true"]'));
+    }
+
+    function dismiss(entry) {
+        var button = entry.modal.querySelector('[data-modal-dismiss]');
+        if (button) button.click();
+    }
+
+    new MutationObserver(function(mutations) {
+        mutations.forEach(function(mutation) {
+            mutation.addedNodes.forEach(function(node) {
+                if (node.nodeType !== 1) return;
+                if (node.matches(MODAL)) open(node);
+                node.querySelectorAll(MODAL).forEach(open);
+            });
+        });
+        prune();
+    }).observe(document.documentElement, { childList: true, subtree: true });
+
+    document.addEventListener('click', function(event) {
+        var close = event.target.closest && event.target.closest('[data-toggle-action="close"]');
+        var modal = close && close.closest(MODAL);
+        if (!modal) return;
+        modal.setAttribute('data-modal-closed', '');
+        prune();
+    });
+
+    window.addEventListener('keydown', function(event) {
+        if (event.key !== 'Escape' || inOpenMenu(event.target)) return;
+        prune();
+        if (!stack.length) return;
+        event.preventDefault();
+        event.stopImmediatePropagation();
+        dismiss(stack[stack.length - 1]);
+    }, true);
+
+    document.addEventListener('modal-close', function(event) {
+        prune();
+        var all = event.detail && event.detail.all;
+        (all ? stack.slice().reverse() : stack.slice(-1)).forEach(dismiss);
+    });
+})();
+"#;
+
+#[derive(Clone)]
 pub enum ModalSize {
     Small,
     Medium,
@@ -17,33 +159,62 @@ pub enum ModalSize {
     Custom(String),
 }
 
+/**
+ * Modal
+ *
+ * `title` and `description` render a standard header and label the dialog.
+ * Without a `title`, the content is expected to provide its own heading, pass
+ * its id (e.g. from `unique_id`) as `title_id`. Ids are generated otherwise, so
+ * several modals on a page never share one. `footer` holds the action
+ * buttons: put the primary action first, it is shown on the right.
+ */
 #[props]
 pub struct ModalProps {
     #[builder(default = ModalSize::Medium)]
     size: ModalSize,
 
+    #[builder(setter(into), default)]
+    title: String,
+
+    #[builder(setter(into), default)]
+    description: String,
+
+    // Id of the heading labelling the dialog, generated when empty.
+    #[builder(setter(into), default)]
+    title_id: String,
+
+    #[builder(setter(into), default)]
+    footer: String,
+
+    #[builder(default = false)]
+    show_close: bool,
+
     #[builder(setter(into))]
     children: String,
 }
 
 #[component]
 pub fn Modal(props: ModalProps) -> String {
+    let title_id = if props.title_id.is_empty() {
+        unique_id("modal-title")
+    } else {
+        props.title_id
+    };
+    let description_id = unique_id("modal-description");
+    let has_description = !props.description.is_empty();
+
     html! {
         <YcControl
             control="modal"
             class="relative z-10"
             role="dialog"
-            aria_labelledby="modal-title"
+            aria_labelledby=title_id.clone()
             attrs=Attrs::with("aria-modal", "true".into())
+                .set_if("aria-describedby", description_id.clone(), has_description)
         >
             <Transition
-                class="fixed inset-0 bg-gray-500 bg-opacity-75 transition-opacity"
-                enter="ease-out duration-300"
-                enter_from="opacity-0"
-                enter_to="opacity-100"
-                leave="ease-in duration-200"
-                leave_from="opacity-100"
-                leave_to="opacity-0"
+                class="fixed inset-0 bg-gray-500 bg-opacity-75"
+                preset=TransitionPreset::Fade
             />
             <div class="fixed inset-0 z-10 w-screen overflow-y-auto">
                 <div class="flex min-h-full items-end justify-center p-4 text-center sm:items-center sm:p-0">
@@ -57,19 +228,74 @@ pub fn Modal(props: ModalProps) -> String {
                                 ModalSize::MediumScreen => "sm:max-w-screen-md".to_string(),
                                 ModalSize::Custom(width) => width,
                             };
-                            format!("relative transform overflow-hidden rounded-lg bg-white px-4 pb-4 pt-5 text-left shadow-xl transition-all sm:my-8 w-full {} sm:p-6", m_width)
+                            format!("relative transform overflow-hidden rounded-lg bg-white px-4 pb-4 pt-5 text-left shadow-xl sm:my-8 w-full {} sm:p-6", m_width)
                         }
                         attrs=Attrs::with("data-modal-panel", "true".into())
-                        enter="ease-out duration-300"
-                        enter_from="opacity-0 translate-y-4 sm:translate-y-0 sm:scale-95"
-                        enter_to="opacity-100 translate-y-0 sm:scale-100"
-                        leave="ease-in duration-200"
-                        leave_from="opacity-100 translate-y-0 sm:scale-100"
-                        leave_to="opacity-0 translate-y-4 sm:translate-y-0 sm:scale-95"
+                        preset=TransitionPreset::Dialog
                         >
+                            <button type="button" class="hidden" data-toggle-action="close" data-modal-dismiss></button>
+                            {
+                                if props.show_close {
+                                    html! {
+                                        <div class="absolute right-0 top-0 hidden pr-4 pt-4 sm:block">
+                                            <button type="button" data-toggle-action="close" class="rounded-md bg-white text-gray-400 hover:text-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2">
+                                                <span class="sr-only">Close</span>
+                                                <svg class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                                                    <path stroke-linecap="round" stroke-linejoin="round" d="M6 18L18 6M6 6l12 12" />
+                                                </svg>
+                                            </button>
+                                        </div>
+                                    }
+                                } else {
+                                    String::new()
+                                }
+                            }
+                            {
+                                if props.title.is_empty() && !has_description {
+                                    String::new()
+                                } else {
+                                    html! {
+                                        <div class="mb-4 sm:pr-8">
+                                            {
+                                                if props.title.is_empty() {
+                                                    String::new()
+                                                } else {
+                                                    html! {
+                                                        <h3 class="text-base font-semibold leading-6 text-gray-900" id=title_id>
+                                                            {props.title}
+                                                        </h3>
+                                                    }
+                                                }
+                                            }
+                                            {
+                                                if has_description {
+                                                    html! {
+                                                        <p class="mt-2 text-sm text-gray-500" id=description_id>
+                                                            {props.description}
+                                                        </p>
+                                                    }
+                                                } else {
+                                                    String::new()
+                                                }
+                                            }
+                                        </div>
+                                    }
+                                }
+                            }
                             <div>
                                 {props.children}
                             </div>
+                            {
+                                if props.footer.is_empty() {
+                                    String::new()
+                                } else {
+                                    html! {
+                                        <div class="mt-5 flex flex-col gap-3 sm:mt-4 sm:flex-row-reverse">
+                                            {props.footer}
+                                        </div>
+                                    }
+                                }
+                            }
                     </Transition>
                 </div>
             </div>
@@ -77,47 +303,234 @@ pub fn Modal(props: ModalProps) -> String {
     }
 }
 
-// TODO! Provide option to show close "X" link in modal
-/*
-    <div class="absolute right-0 top-0 hidden pr-4 pt-4 sm:block">
-        <button type="button" class="rounded-md bg-white text-gray-400 hover:text-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2">
-            <span class="sr-only">Close</span>
-            <svg class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
-                <path stroke-linecap="round" stroke-linejoin="round" d="M6 18L18 6M6 6l12 12" />
-            </svg>
-        </button>
-    </div>
-*/
-
+/**
+ * ConfirmDeleteModal
+ *
+ * Pre-rendered dialog for `hx-confirm` requests. Texts, labels and variant
+ * are filled in from the `Confirm` of the requesting element, see `CONFIRM_SCRIPT`.
+ */
 #[component]
 pub fn ConfirmDeleteModal() -> String {
+    let default_variant = ConfirmVariant::default();
+    let title_id = unique_id("confirm-title");
+
     html! {
-        <Modal>
+        <Modal
+            title_id=title_id.clone()
+            footer=html! {
+                <button
+                    type="button"
+                    class=default_variant.button_class()
+                    data-toggle-action="close"
+                    data-confirm-action="delete"
+                    data-confirm-default-label="Delete"
+                    data-confirm-class-danger=ConfirmVariant::Danger.button_class()
+                    data-confirm-class-warning=ConfirmVariant::Warning.button_class()
+                    data-confirm-class-neutral=ConfirmVariant::Neutral.button_class()
+                >
+                    Delete
+                </button>
+                <button
+                    type="button"
+                    class="inline-flex w-full justify-center rounded-md bg-white px-3 py-2 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50 sm:w-auto"
+                    data-toggle-action="close"
+                    data-confirm-cancel
+                    data-confirm-default-label="Cancel"
+                >
+                    Cancel
+                </button>
+            }
+        >
             <div class="sm:flex sm:items-start">
-                <div class="mx-auto flex h-12 w-12 flex-shrink-0 items-center justify-center rounded-full bg-red-100 sm:mx-0 sm:h-10 sm:w-10">
-                    <svg class="h-6 w-6 text-red-600" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
-                        <path stroke-linecap="round" stroke-linejoin="round" d="M12 9v3.75m-9.303 3.376c-.866 1.5.217 3.374 1.948 3.374h14.71c1.73 0 2.813-1.874 1.948-3.374L13.949 3.378c-.866-1.5-3.032-1.5-3.898 0L2.697 16.126zM12 15.75h.007v.008H12v-.008z" />
-                    </svg>
-                </div>
-                <div class="mt-3 text-center sm:ml-4 sm:mt-0 sm:text-left">
-                    <h3 class="text-base font-semibold leading-6 text-gray-900" id="modal-title" data-confirm-delete-title>Deactivate account</h3>
+                {
+                    ConfirmVariant::ALL
+                        .iter()
+                        .map(|variant| html! {
+                            <div
+                                class=format!(
+                                    "mx-auto flex h-12 w-12 flex-shrink-0 items-center justify-center rounded-full sm:mx-0 sm:h-10 sm:w-10 {} {}",
+                                    variant.icon_wrapper_class(),
+                                    if *variant == default_variant { "" } else { "hidden" },
+                                ).trim()
+                                data-confirm-icon=variant.as_str()
+                            >
+                                {variant.icon()}
+                            </div>
+                        })
+                        .collect_fragment()
+                }
+                <div class="mt-3 w-full text-center sm:ml-4 sm:mt-0 sm:text-left">
+                    <h3 class="text-base font-semibold leading-6 text-gray-900" id=title_id data-confirm-delete-title>Are you sure?</h3>
                     <div class="mt-2">
-                        <p class="text-sm text-gray-500" data-confirm-delete-message>Are you sure you want to delete this item?.</p>
+                        <p class="text-sm text-gray-500" data-confirm-delete-message>Are you sure you want to delete this item?</p>
+                    </div>
+                    <div class="mt-4 hidden" data-confirm-input-group>
+                        <label class="block text-sm text-gray-700">
+                            "Type " <strong data-confirm-text></strong> " to confirm."
+                        </label>
+                        <input
+                            type="text"
+                            autocomplete="off"
+                            class="mt-2 block w-full rounded-md border-0 py-1.5 text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 focus:ring-2 focus:ring-inset focus:ring-indigo-600 sm:text-sm sm:leading-6"
+                            data-confirm-input
+                        />
                     </div>
                 </div>
             </div>
-            <div class="mt-5 sm:mt-4 sm:flex sm:flex-row-reverse">
-                <button data-toggle-action="close" data-confirm-action="delete" type="button" class="inline-flex w-full justify-center rounded-md bg-red-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-red-500 sm:ml-3 sm:w-auto">Delete</button>
-                <button data-toggle-action="close" type="button" class="mt-3 inline-flex w-full justify-center rounded-md bg-white px-3 py-2 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50 sm:mt-0 sm:w-auto">Cancel</button>
-            </div>
         </Modal>
     }
 }
 
+/**
+ * ModalForm
+ *
+ * A `Modal` holding a form with Save/Cancel actions. Set the request attrs
+ * (`hx_post`, `hx_put`...) as on a `<form>`. The handler should answer with a
+ * `ModalFormResponse`: `Invalid` swaps in the `ModalFormBody` again (with the
+ * errors) without replaying the modal's transition, `Success` closes the modal.
+ */
+#[html_element]
+pub struct ModalFormProps {
+    #[builder(setter(into), default)]
+    title: String,
+
+    #[builder(setter(into), default)]
+    description: String,
+
+    #[builder(default = ModalSize::Medium)]
+    size: ModalSize,
+
+    #[builder(setter(into), default = "Save".into())]
+    submit_label: String,
+
+    #[builder(setter(into), default = "Cancel".into())]
+    cancel_label: String,
+
+    children: String,
+}
+
+#[component]
+pub fn ModalForm(props: ModalFormProps) -> String {
+    html! {
+        <Modal
+            title=props.title
+            description=props.description
+            size=props.size
+            show_close=true
+        >
+            <ModalFormBody
+                submit_label=props.submit_label
+                cancel_label=props.cancel_label
+                class=props.class
+                attrs=spread_attrs!(props | omit(class))
+            >
+                {props.children}
+            </ModalFormBody>
+        </Modal>
+    }
+}
+
+/**
+ * ModalFormBody
+ *
+ * The form inside a `ModalForm`. Render it with the same attrs when
+ * responding with `ModalFormResponse::Invalid`.
+ */
+#[html_element]
+pub struct ModalFormBodyProps {
+    #[builder(setter(into), default = "Save".into())]
+    submit_label: String,
+
+    #[builder(setter(into), default = "Cancel".into())]
+    cancel_label: String,
+
+    children: String,
+}
+
+#[component]
+pub fn ModalFormBody(props: ModalFormBodyProps) -> String {
+    html! {
+        <HtmlElement
+            tag="form"
+            class=props.class.clone()
+            attrs=spread_attrs!(props | omit(class))
+                .set("hx-target", "this".into())
+                .set("hx-swap", "outerHTML".into())
+                .set("data-modal-form", "true".into())
+        >
+            {props.children}
+            <div class="mt-5 flex flex-col gap-3 sm:mt-4 sm:flex-row-reverse">
+                <Button kind="submit">{props.submit_label}</Button>
+                <SecondaryButton
+                    class="px-3 py-2"
+                    attrs=Attrs::with("data-toggle-action", "close".into())
+                >
+                    {props.cancel_label}
+                </SecondaryButton>
+            </div>
+        </HtmlElement>
+    }
+}
+
+// Event triggered on a successful `ModalForm` submit, e.g. listen with
+// `hx-trigger="refresh-users from:body"` to reload the "users" list.
+pub fn refresh_event(list: &str) -> String {
+    format!("refresh-{}", list)
+}
+
+pub enum ModalFormResponse {
+    // The re-rendered `ModalFormBody`, e.g. with validation errors.
+    Invalid(String),
+    Success {
+        // Name of the list to refresh, see `refresh_event`.
+        refresh: Option<String>,
+        notification: Option<NotificationCall>,
+    },
+}
+
+impl ModalFormResponse {
+    pub async fn respond(self) -> Response {
+        match self {
+            ModalFormResponse::Invalid(form) => Html(form).into_response(),
+            ModalFormResponse::Success {
+                refresh,
+                notification,
+            } => {
+                let mut trigger = match refresh {
+                    Some(list) => close_modal().event(refresh_event(&list)),
+                    None => close_modal(),
+                };
+                let notification = match notification {
+                    Some(call) => match call.to_hx_trigger() {
+                        Some(notify) => {
+                            trigger = trigger.merge(notify);
+                            String::new()
+                        }
+                        None => html! { <NotificationPresenter call=call /> },
+                    },
+                    None => String::new(),
+                };
+
+                (
+                    trigger,
+                    [
+                        ("HX-Retarget", "body".to_string()),
+                        ("HX-Reswap", "beforeend".to_string()),
+                    ],
+                    Html(notification),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
 /**
  * ModalLiveRegion
  *
  * Holds all pre-rendered modals to to be rendered client-side by Modals control.
+ * Modals opened on top of another one (e.g. a confirm from an edit form)
+ * are stacked above it, see `MODAL_SCRIPT`.
  */
 #[component]
 pub fn ModalLiveRegion() -> String {
@@ -131,3 +544,100 @@ pub fn ModalLiveRegion() -> String {
       </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_title_and_description_label_the_dialog() {
+        let html = html! {
+            <Modal title="Edit user" description="Changes are saved right away.">
+                <p>Form</p>
+            </Modal>
+        }
+        .replace("&#x2D;", "-");
+
+        let title_id = html
+            .split("aria-labelledby=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+        let description_id = html
+            .split("aria-describedby=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+
+        assert_ne!(title_id, "modal-title");
+        assert!(html.contains(&format!("id=\"{}\"", title_id)));
+        assert!(html.contains(&format!("id=\"{}\"", description_id)));
+    }
+
+    #[tokio::test]
+    async fn test_without_title_generates_title_id() {
+        let html = html! {
+            <Modal>
+                <p>Custom</p>
+            </Modal>
+        }
+        .replace("&#x2D;", "-");
+
+        assert!(html.contains("aria-labelledby=\"modal-title-"));
+        assert!(!html.contains("aria-labelledby=\"modal-title\""));
+        assert!(!html.contains("aria-describedby"));
+        assert!(!html.contains(">Close</span>"));
+    }
+
+    #[tokio::test]
+    async fn test_title_id_labels_the_dialog() {
+        let html = html! {
+            <Modal title_id="invite-heading" show_close=true>
+                <h3 id="invite-heading">Invite</h3>
+            </Modal>
+        }
+        .replace("&#x2D;", "-");
+
+        assert!(html.contains("role=\"dialog\""));
+        assert!(html.contains("aria-modal=\"true\""));
+        assert!(html.contains("aria-labelledby=\"invite-heading\""));
+        assert!(html.contains(">Close</span>"));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_delete_modal_ids_are_unique() {
+        let labelled_by = |html: String| {
+            let html = html.replace("&#x2D;", "-");
+            let id = html
+                .split("aria-labelledby=\"")
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .unwrap()
+                .to_string();
+            assert!(html.contains(&format!("id=\"{}\" data-confirm-delete-title", id)));
+            id
+        };
+
+        let first = labelled_by(html! { <ConfirmDeleteModal /> });
+        let second = labelled_by(html! { <ConfirmDeleteModal /> });
+
+        assert!(first.starts_with("confirm-title-"));
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_modal_form_success_closes_and_refreshes() {
+        let response = ModalFormResponse::Success {
+            refresh: Some("users".into()),
+            notification: None,
+        }
+        .respond()
+        .await;
+
+        assert_eq!(
+            response.headers().get("HX-Trigger").unwrap(),
+            r#"{"modal-close":{"all":false},"refresh-users":null}"#
+        );
+        assert_eq!(response.headers().get("HX-Reswap").unwrap(), "beforeend");
+    }
+}
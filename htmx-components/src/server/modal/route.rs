@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use axum::{
+    http::{HeaderMap, HeaderValue},
+    response::{IntoResponseParts, ResponseParts},
+};
+use rscx::{component, html, props};
+
+use super::modal_target;
+
+pub const MODAL_PARAM: &str = "modal";
+
+// Value of the `modal` query param, if any.
+pub fn modal_param(query_params: &HashMap<String, String>) -> Option<String> {
+    query_params
+        .get(MODAL_PARAM)
+        .filter(|modal| !modal.is_empty())
+        .cloned()
+}
+
+// The param comes from shareable links, so only same-origin paths are loaded.
+// The path (up to "?" or "#") must not start with "//" or contain a backslash (read as
+// "/" by browsers), and its first segment can't hold a ":" ("https:", "javascript:"...).
+// Control characters, which browsers strip from URLs, are rejected anywhere.
+// Query and fragment are free, e.g. "/redirect?to=https://example.com".
+fn is_same_origin_route(modal: &str) -> bool {
+    let path = modal.split(['?', '#']).next().unwrap_or_default();
+    let first_segment = path.split('/').next().unwrap_or_default();
+
+    !path.starts_with("//")
+        && !path.contains('\\')
+        && !first_segment.contains(':')
+        && !modal.chars().any(|c| c.is_control())
+}
+
+// Routes starting with "/" are absolute, others are relative to the page.
+fn modal_url(page_url: &str, modal: &str) -> Option<String> {
+    if !is_same_origin_route(modal) {
+        None
+    } else if modal.starts_with('/') {
+        Some(modal.to_string())
+    } else {
+        Some(format!("{}/{}", page_url.trim_end_matches('/'), modal))
+    }
+}
+
+fn split_url(url: &str) -> (&str, &str, &str) {
+    let (url, fragment) = url.split_once('#').unwrap_or((url, ""));
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    (path, query, fragment)
+}
+
+// `url` with its `modal` param set to `modal`.
+fn url_with_modal(url: &str, modal: &str) -> String {
+    let (path, query, fragment) = split_url(url);
+
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(
+            form_urlencoded::parse(query.as_bytes()).filter(|(key, _)| key != MODAL_PARAM),
+        )
+        .append_pair(MODAL_PARAM, modal)
+        .finish();
+
+    match fragment {
+        "" => format!("{}?{}", path, query),
+        fragment => format!("{}?{}#{}", path, query, fragment),
+    }
+}
+
+fn current_modal(url: &str) -> Option<String> {
+    let (_, query, _) = split_url(url);
+
+    form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == MODAL_PARAM)
+        .map(|(_, value)| value.into_owned())
+}
+
+/**
+ * ModalRoutePush
+ *
+ * Response part that adds `?modal=...` to the browser URL (via `HX-Push-Url`)
+ * when a modal opens, so the link can be shared and the modal is restored by
+ * `ModalRoute` on load. Closing the modal removes the param again.
+ */
+pub struct ModalRoutePush(Option<String>);
+
+// `headers` are the request headers, `modal` is the route the modal is loaded from.
+pub fn push_modal_route(headers: &HeaderMap, modal: &str) -> ModalRoutePush {
+    let push_url = headers
+        .get("HX-Current-URL")
+        .and_then(|url| url.to_str().ok())
+        // Already there when the modal is restored from the URL.
+        .filter(|url| current_modal(url).as_deref() != Some(modal))
+        .map(|url| {
+            // Keep only path, query and fragment of the absolute URL.
+            let url = match url.split_once("://") {
+                Some((_, rest)) => rest.find('/').map(|i| &rest[i..]).unwrap_or("/"),
+                None => url,
+            };
+            url_with_modal(url, modal)
+        });
+
+    ModalRoutePush(push_url)
+}
+
+impl IntoResponseParts for ModalRoutePush {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if let Some(value) = self.0.and_then(|url| HeaderValue::from_str(&url).ok()) {
+            res.headers_mut().insert("HX-Push-Url", value);
+        }
+
+        Ok(res)
+    }
+}
+
+/**
+ * ModalRoute
+ *
+ * Place once per page. Opens the modal of the `modal` query param on load.
+ */
+#[props]
+pub struct ModalRouteProps {
+    // Path of the current page, relative modal routes are resolved against it.
+    #[builder(setter(into))]
+    page_url: String,
+
+    // See `modal_param`.
+    #[builder(default)]
+    modal: Option<String>,
+}
+
+#[component]
+pub fn ModalRoute(props: ModalRouteProps) -> String {
+    match props
+        .modal
+        .and_then(|modal| modal_url(&props.page_url, &modal))
+    {
+        Some(url) => html! {
+            <div
+                hx-get=url
+                hx-trigger="load"
+                hx-target=format!("{} [data-modal-content]", modal_target())
+                hx-swap="beforeend"
+                data-modal-route
+            />
+        },
+        None => String::new(),
+    }
+}
+
+// Drops `?modal=` once the last modal closes and reopens or closes routed
+// modals on back/forward, before htmx restores its history snapshot.
+pub(crate) const MODAL_ROUTE_SCRIPT: &str = r#"
+(function() {
+    var PARAM = 'modal';
+    var lastUrl = location.href;
+    var restoring = false;
+
+    function modalParam(url) {
+        return new URL(url, location.href).searchParams.get(PARAM);
+    }
+
+    // Same checks as `is_same_origin_route`, then resolved and compared to our origin.
+    function modalUrl(modal) {
+        var path = modal.split(/[?#]/)[0];
+        if (path.indexOf('//') === 0 || path.indexOf('\\') !== -1 || path.split('/')[0].indexOf(':') !== -1) return null;
+        if (/[\u0000-\u001f\u007f-\u009f]/.test(modal)) return null;
+
+        var path = modal.charAt(0) === '/' ? modal : location.pathname.replace(/\/$/, '') + '/' + modal;
+        var url = new URL(path, location.href);
+        return url.origin === location.origin ? url.pathname + url.search + url.hash : null;
+    }
+
+    function withoutModal(url) {
+        var parsed = new URL(url, location.href);
+        parsed.searchParams.delete(PARAM);
+        return parsed.pathname + parsed.search + parsed.hash;
+    }
+
+    document.addEventListener('modal-stack-empty', function() {
+        if (restoring || !modalParam(location.href)) return;
+        history.pushState({}, '', withoutModal(location.href));
+        lastUrl = location.href;
+    });
+
+    ['htmx:pushedIntoHistory', 'htmx:replacedInHistory'].forEach(function(name) {
+        document.addEventListener(name, function() { lastUrl = location.href; });
+    });
+
+    window.addEventListener('popstate', function(event) {
+        var from = lastUrl;
+        lastUrl = location.href;
+        if (withoutModal(from) !== withoutModal(location.href)) return;
+        event.stopImmediatePropagation();
+
+        var modal = modalParam(location.href);
+        restoring = true;
+        document.dispatchEvent(new CustomEvent('modal-close', { detail: { all: true } }));
+        restoring = false;
+
+        var url = modal && modalUrl(modal);
+        if (url) {
+            htmx.ajax('GET', url, { target: '#modal-live-region [data-modal-content]', swap: 'beforeend' });
+        }
+    }, true);
+})();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(current_url: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("HX-Current-URL", current_url.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_push_modal_route() {
+        let ModalRoutePush(url) = push_modal_route(
+            &headers("http://localhost:3000/customers?page=2"),
+            "/customers/42/edit",
+        );
+
+        assert_eq!(
+            url.as_deref(),
+            Some("/customers?page=2&modal=%2Fcustomers%2F42%2Fedit")
+        );
+    }
+
+    #[test]
+    fn test_push_modal_route_replaces_and_skips() {
+        let ModalRoutePush(url) = push_modal_route(
+            &headers("http://localhost:3000/customers?modal=new&page=2#notes"),
+            "edit a&b",
+        );
+        assert_eq!(
+            url.as_deref(),
+            Some("/customers?page=2&modal=edit+a%26b#notes")
+        );
+
+        let ModalRoutePush(url) = push_modal_route(
+            &headers("http://localhost:3000/customers?modal=%2Fcustomers%2F42%2Fedit"),
+            "/customers/42/edit",
+        );
+        assert_eq!(url, None);
+    }
+
+    #[test]
+    fn test_modal_url() {
+        assert_eq!(
+            modal_url("/customers", "42/edit").as_deref(),
+            Some("/customers/42/edit")
+        );
+        assert_eq!(
+            modal_url("/customers/", "/other").as_deref(),
+            Some("/other")
+        );
+        assert_eq!(
+            modal_url("/customers", "/redirect?to=https://example.com").as_deref(),
+            Some("/redirect?to=https://example.com")
+        );
+    }
+
+    #[test]
+    fn test_modal_url_rejects_other_origins() {
+        for modal in [
+            "//evil.host/x",
+            "/\\evil.host/x",
+            "\\\\evil.host/x",
+            "https://evil.host/x",
+            "javascript:alert(1)",
+            "/\t/evil.host/x",
+            "/\n/evil.host/x",
+        ] {
+            assert_eq!(modal_url("/customers", modal), None, "{:?}", modal);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_modal_route_ignores_other_origins() {
+        let html = html! {
+            <ModalRoute page_url="/customers" modal=Some("//evil.host/x".to_string()) />
+        };
+        assert_eq!(html, "");
+
+        let html = html! {
+            <ModalRoute page_url="/customers" modal=Some("42/edit".to_string()) />
+        };
+        assert!(html.contains("data-modal-route"));
+    }
+}
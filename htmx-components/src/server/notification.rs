@@ -1,19 +1,86 @@
-use super::transition::Transition;
+use super::hx_trigger::HxTrigger;
+use super::transition::{Transition, TransitionPreset};
 use super::yc_control::YcControlJsApi;
 use rscx::{component, html, props, CollectFragmentAsync};
+use serde_json::{json, Value};
+use std::{fmt, time::Duration};
+
+pub mod flash;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NotificationPosition {
+    TopLeft,
+    TopCenter,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl NotificationPosition {
+    // Notifications are at the bottom on small screens, wherever the position.
+    fn classes(&self) -> (&'static str, &'static str) {
+        match self {
+            NotificationPosition::TopLeft => ("sm:items-start", "sm:items-start"),
+            NotificationPosition::TopCenter => ("sm:items-start", ""),
+            NotificationPosition::TopRight => ("sm:items-start", "sm:items-end"),
+            NotificationPosition::BottomLeft => ("", "sm:items-start"),
+            NotificationPosition::BottomRight => ("", "sm:items-end"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NotificationOrder {
+    NewestFirst,
+    #[default]
+    NewestLast,
+}
+
+impl NotificationOrder {
+    fn as_str(&self) -> &'static str {
+        match self {
+            NotificationOrder::NewestFirst => "newest-first",
+            NotificationOrder::NewestLast => "newest-last",
+        }
+    }
+}
 
 /**
  * NotificationLiveRegion
  *
  * Holds all attached notifications to show
  * Also contains all standard templates.
+ * With `max_visible`, older notifications are collapsed until newer ones are closed.
  */
+#[props]
+pub struct NotificationLiveRegionProps {
+    #[builder(default)]
+    position: NotificationPosition,
+
+    #[builder(default)]
+    max_visible: Option<usize>,
+
+    #[builder(default)]
+    order: NotificationOrder,
+}
 
 #[component]
-pub fn NotificationLiveRegion() -> String {
+pub fn NotificationLiveRegion(props: NotificationLiveRegionProps) -> String {
+    let (vertical, horizontal) = props.position.classes();
+
     html! {
-        <div id="notification-live-region" aria-live="assertive" class="pointer-events-none fixed inset-0 flex items-end px-4 py-6 sm:items-start sm:p-6">
-            <section class="flex w-full flex-col items-center space-y-4 sm:items-end" data-notification-content>
+        <div
+            id="notification-live-region"
+            aria-live="assertive"
+            class=format!("pointer-events-none fixed inset-0 flex items-end px-4 py-6 sm:p-6 {}", vertical).trim()
+        >
+            <section
+                class=format!("flex w-full flex-col items-center space-y-4 {}", horizontal).trim()
+                data-notification-content
+                data-notification-order=props.order.as_str()
+                data-notification-max-visible=props.max_visible.map(|max| max.to_string()).unwrap_or_default()
+            >
             </section>
             <template id="tpl-notification">
                 <SimpleNotification icon_svg=IconSvg::Info />
@@ -22,6 +89,8 @@ pub fn NotificationLiveRegion() -> String {
                 <NotificationIcon svg=IconSvg::Success/>
                 <NotificationIcon svg=IconSvg::Error/>
                 <NotificationIcon svg=IconSvg::Info/>
+                <NotificationIcon svg=IconSvg::Warning/>
+                <NotificationIcon svg=IconSvg::Debug/>
                 // Add any additional prerendered icons here.
             </template>
         </div>
@@ -54,10 +123,12 @@ pub fn SimpleNotification(props: SimpleNotificationProps) -> String {
                     <div class="ml-3 w-0 flex-1 pt-0.5">
                         <p class="text-sm font-medium text-gray-900" data-notification-title>{props.title}</p>
                         <p class="mt-1 text-sm text-gray-500" data-notification-message>{props.message}</p>
+                        <div class="mt-3 hidden space-x-7" data-notification-actions></div>
                     </div>
                     <NoticationCloseButton />
                 </div>
             </div>
+            <div class="hidden h-1 w-full bg-indigo-500" data-notification-progress></div>
         </NotificationTransition>
     }
 }
@@ -66,6 +137,8 @@ pub enum IconSvg {
     Success,
     Error,
     Info,
+    Warning,
+    Debug,
     Custom(String),
 }
 
@@ -98,6 +171,16 @@ fn NotificationIcon(props: NotificationIconProps) -> String {
                 <path stroke-linecap="round" stroke-linejoin="round" d="M11.25 11.25l.041-.02a.75.75 0 011.063.852l-.708 2.836a.75.75 0 001.063.853l.041-.021M21 12a9 9 0 11-18 0 9 9 0 0118 0zm-9-3.75h.008v.008H12V8.25z" />
             </svg>
         },
+        IconSvg::Warning => html! {
+            <svg class="h-6 w-6 text-yellow-400" data-notification-icon="warning" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                <path stroke-linecap="round" stroke-linejoin="round" d="M12 9v3.75m-9.303 3.376c-.866 1.5.217 3.374 1.948 3.374h14.71c1.73 0 2.813-1.874 1.948-3.374L13.949 3.378c-.866-1.5-3.032-1.5-3.898 0L2.697 16.126zM12 15.75h.007v.008H12v-.008z" />
+            </svg>
+        },
+        IconSvg::Debug => html! {
+            <svg class="h-6 w-6 text-gray-400" data-notification-icon="debug" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                <path stroke-linecap="round" stroke-linejoin="round" d="M17.25 6.75L22.5 12l-5.25 5.25m-10.5 0L1.5 12l5.25-5.25m7.5-3l-4.5 16.5" />
+            </svg>
+        },
         IconSvg::Custom(svg) => svg,
     }
 }
@@ -121,83 +204,281 @@ pub fn NotificationFlashes(props: NotificationFlashesProps) -> String {
         .flashes
         .into_iter()
         .map(|(level, message)| async move {
-            let js_notification_fn = match level {
-                axum_flash::Level::Success => "showSuccessNotification",
-                axum_flash::Level::Error => "showErrorNotification",
-                _ => "showErrorNotification", // TODO! Replace with generic notification.
-            };
-
-            let message = serde_json::to_string(&message).unwrap();
-
             html! {
-                <YcControlJsApi call=format!("{}({})", js_notification_fn, message) />
+                <NotificationPresenter call=NotificationCall::from_flash(level, message.into()) />
             }
         })
         .collect_fragment_async()
         .await
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationLevel {
+    Success,
+    Error,
+    Info,
+    Warning,
+    Debug,
+}
+
+impl NotificationLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationLevel::Success => "success",
+            NotificationLevel::Error => "error",
+            NotificationLevel::Info => "info",
+            NotificationLevel::Warning => "warning",
+            NotificationLevel::Debug => "debug",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            NotificationLevel::Success => "Success",
+            NotificationLevel::Error => "Error",
+            NotificationLevel::Info => "Info",
+            NotificationLevel::Warning => "Warning",
+            NotificationLevel::Debug => "Debug",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotificationAction {
+    pub label: String,
+    // Posted with `hx-post` when clicked, the notification is dismissed.
+    pub hx_post: String,
+}
+
+// Notifications show at most this many actions.
+pub const MAX_NOTIFICATION_ACTIONS: usize = 2;
+
+/**
+ * Notification
+ *
+ * A standard notification with options. It dismisses itself after `timeout`,
+ * showing the time left as a progress bar, unless it is `sticky`. Actions,
+ * e.g. an "Undo" after a delete, are buttons posting to the server.
+ * A notification with the `id` of one still shown replaces its content, e.g.
+ * to report the progress of a long running job in a single notification.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub id: Option<String>,
+    pub level: NotificationLevel,
+    pub title: String,
+    pub message: String,
+    // `None` keeps the notification open until closed.
+    pub timeout: Option<Duration>,
+    pub actions: Vec<NotificationAction>,
+}
+
+impl Notification {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub fn new(level: NotificationLevel, message: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            level,
+            title: level.title().into(),
+            message: message.into(),
+            timeout: Some(Self::DEFAULT_TIMEOUT),
+            actions: vec![],
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn sticky(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    // Actions beyond `MAX_NOTIFICATION_ACTIONS` are ignored.
+    pub fn action(mut self, label: impl Into<String>, hx_post: impl Into<String>) -> Self {
+        if self.actions.len() < MAX_NOTIFICATION_ACTIONS {
+            self.actions.push(NotificationAction {
+                label: label.into(),
+                hx_post: hx_post.into(),
+            });
+        }
+        self
+    }
+
+    // Options for `YcControls.notify`.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "level": self.level.as_str(),
+            "title": self.title,
+            "message": self.message,
+            "timeout": self.timeout.map(|timeout| timeout.as_millis() as u64),
+            "actions": self
+                .actions
+                .iter()
+                .map(|action| json!({ "label": action.label, "hxPost": action.hx_post }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
 pub enum NotificationCall {
     Success(String),
     Error(String),
     Info(String, String), // title, message
-    Template,
+    Warning(String),
+    Debug(String),
+    Notification(Notification),
+    // Markup of a notification, shown like the standard ones.
+    Template(String),
     TemplateSelector(String),
 }
 
+#[derive(Debug)]
+pub enum NotificationError {
+    EmptyTemplate,
+    Encode(serde_json::Error),
+}
+
+impl fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationError::EmptyTemplate => write!(f, "template call without template"),
+            NotificationError::Encode(error) => write!(f, "can not encode notification: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for NotificationError {}
+
+impl From<serde_json::Error> for NotificationError {
+    fn from(error: serde_json::Error) -> Self {
+        NotificationError::Encode(error)
+    }
+}
+
+// Event `NotificationCall::to_hx_trigger` triggers, shown by the `NotificationLiveRegion`.
+pub const NOTIFY_EVENT: &str = "notify";
+
+impl NotificationCall {
+    pub fn from_flash(level: axum_flash::Level, message: String) -> Self {
+        match level {
+            axum_flash::Level::Debug => NotificationCall::Debug(message),
+            axum_flash::Level::Info => NotificationCall::Info("Info".into(), message),
+            axum_flash::Level::Success => NotificationCall::Success(message),
+            axum_flash::Level::Warning => NotificationCall::Warning(message),
+            axum_flash::Level::Error => NotificationCall::Error(message),
+        }
+    }
+
+    // The call as standard notification, `None` for template calls.
+    pub fn to_notification(&self) -> Option<Notification> {
+        let notification = match self {
+            NotificationCall::Success(message) => {
+                Notification::new(NotificationLevel::Success, message)
+            }
+            NotificationCall::Error(message) => {
+                Notification::new(NotificationLevel::Error, message)
+            }
+            NotificationCall::Info(title, message) => {
+                Notification::new(NotificationLevel::Info, message).title(title)
+            }
+            NotificationCall::Warning(message) => {
+                Notification::new(NotificationLevel::Warning, message)
+            }
+            NotificationCall::Debug(message) => {
+                Notification::new(NotificationLevel::Debug, message)
+            }
+            NotificationCall::Notification(notification) => notification.clone(),
+            NotificationCall::Template(_) | NotificationCall::TemplateSelector(_) => return None,
+        };
+
+        Some(notification)
+    }
+
+    // The notification as `HX-Trigger` event, e.g. for responses swapping nothing
+    // into the page. `None` for `Template`, which needs its template rendered.
+    pub fn to_hx_trigger(&self) -> Option<HxTrigger> {
+        let detail = match self {
+            NotificationCall::TemplateSelector(selector) => json!({ "template": selector }),
+            call => call.to_notification()?.to_json(),
+        };
+
+        Some(HxTrigger::new().event_with(NOTIFY_EVENT, detail))
+    }
+}
+
 /**
  * NotificationPresenter
  *
  * Use this to present a notification from a server resource.
+ * Calls which can not be rendered are logged (with `tracing`) and left out,
+ * use `NotificationCall::render` to handle the error yourself.
  */
 
 #[props]
 pub struct NotificationPresenterProps {
     call: NotificationCall,
-
-    #[builder(default)]
-    children: String,
 }
 
-fn js_enc<T>(data: &T) -> String
+fn js_enc<T>(data: &T) -> Result<String, serde_json::Error>
 where
     T: ?Sized + serde::Serialize,
 {
-    serde_json::to_string::<T>(data).unwrap()
+    serde_json::to_string::<T>(data)
 }
 
-#[component]
-pub fn NotificationPresenter(props: NotificationPresenterProps) -> String {
-    let api_call = match props.call {
-        NotificationCall::Success(message) => {
-            format!("showSuccessNotification({})", js_enc(&message))
-        }
-        NotificationCall::Error(message) => {
-            format!("showErrorNotification({})", js_enc(&message))
-        }
-        NotificationCall::Info(title, message) => {
-            format!("showNotification({}, {})", js_enc(&title), js_enc(&message))
-        }
-        NotificationCall::TemplateSelector(templateSelector) => {
-            format!(
-                "showNotificationWithTemplate({})",
-                js_enc(&templateSelector),
-            )
-        }
-        NotificationCall::Template => {
-            if props.children.is_empty() {
-                panic!("NotificationPresenter: Template call requires children.")
+impl NotificationCall {
+    // Script presenting the notification, see `NotificationPresenter`.
+    pub async fn render(self) -> Result<String, NotificationError> {
+        let (api_call, template) = match self {
+            NotificationCall::TemplateSelector(selector) => (
+                format!("showNotificationWithTemplate({})", js_enc(&selector)?),
+                String::new(),
+            ),
+            NotificationCall::Template(template) if template.trim().is_empty() => {
+                return Err(NotificationError::EmptyTemplate);
             }
-            "showNotificationWithTemplate(callerScript.nextElementSibling)".into()
-        }
-    };
+            NotificationCall::Template(template) => (
+                "showNotificationWithTemplate(callerScript.nextElementSibling)".into(),
+                html! { <template>{template}</template> },
+            ),
+            call => {
+                let notification = call
+                    .to_notification()
+                    .map(|notification| notification.to_json());
+                (format!("notify({})", js_enc(&notification)?), String::new())
+            }
+        };
 
-    html! {
-        <YcControlJsApi call=api_call />
-        {props.children}
+        Ok(html! {
+            <YcControlJsApi call=api_call />
+            {template}
+        })
     }
 }
 
+#[component]
+pub fn NotificationPresenter(props: NotificationPresenterProps) -> String {
+    props.call.render().await.unwrap_or_else(|error| {
+        tracing::error!(%error, "NotificationPresenter could not render the notification");
+        String::new()
+    })
+}
+
 // #### Notification components to help you build your own. ###############
 
 #[props]
@@ -214,10 +495,7 @@ pub fn NotificationTransition(props: NotificationTransitionProps) -> String {
     html! {
         <Transition
             class=format!("pointer-events-auto {}", props.class).trim()
-            enter="transform ease-out duration-300 transition"
-            enter_from="translate-y-2 opacity-0 sm:translate-y-0 sm:translate-x-2"
-            enter_to="translate-y-0 opacity-100 sm:translate-x-0"
-            leave="transition ease-in duration-300"
+            preset=TransitionPreset::Toast
             leave_from="opacity-100"
             leave_to="opacity-0"
         >
@@ -239,3 +517,247 @@ pub fn NoticationCloseButton() -> String {
         </div>
     }
 }
+
+// Lays out the `NotificationLiveRegion` and adds
+// `YcControls.notify({ level, title, message, timeout, actions })`, which shows
+// the standard notification with the icon of `level` from the region templates.
+// `timeout` is in ms, `null` keeps it open, `actions` are `{ label, hxPost }`.
+// A notification with the `id` of a shown one updates it in place.
+// Also shows the notifications of `notify` events (see `NotificationCall::to_hx_trigger`),
+// a single one or a list of them.
+pub(crate) const NOTIFICATION_SCRIPT: &str = r#"
+document.addEventListener('notify', function(event) {
+    var detail = event.detail || {};
+    var notifications = Array.isArray(detail.value) ? detail.value : [detail];
+    notifications.forEach(function(notification) {
+        if (notification.template) {
+            YcControls.showNotificationWithTemplate(notification.template);
+        } else {
+            YcControls.notify(notification);
+        }
+    });
+});
+
+YcControls.onReady(function() {
+    var content = document.querySelector('#notification-live-region [data-notification-content]');
+    if (!content) return;
+
+    // Keeps the order and collapses notifications beyond the max visible.
+    function layout() {
+        var newestFirst = content.getAttribute('data-notification-order') === 'newest-first';
+        var maxVisible = parseInt(content.getAttribute('data-notification-max-visible'), 10);
+        var notifications = Array.prototype.slice.call(content.children);
+        if (newestFirst) notifications.reverse();
+
+        notifications.forEach(function(notification, i) {
+            var collapsed = maxVisible > 0 && i < notifications.length - maxVisible;
+            notification.style.display = collapsed ? 'none' : '';
+        });
+    }
+
+    new MutationObserver(function(mutations) {
+        if (content.getAttribute('data-notification-order') === 'newest-first') {
+            mutations.forEach(function(mutation) {
+                mutation.addedNodes.forEach(function(node) {
+                    if (node.nodeType === 1 && node.parentNode === content && node !== content.firstElementChild) {
+                        content.insertBefore(node, content.firstElementChild);
+                    }
+                });
+            });
+        }
+        layout();
+    }).observe(content, { childList: true });
+});
+
+YcControls.onReady(function() {
+    var DEFAULT_TIMEOUT = 5000;
+    var MAX_ACTIONS = 2;
+
+    function region() {
+        return document.querySelector('#notification-live-region [data-notification-content]');
+    }
+
+    function dismiss(notification) {
+        var close = notification.querySelector('button[data-notification-close]');
+        if (close) close.click();
+    }
+
+    function fill(root, options) {
+        var icon = document.getElementById('tpl-notification-icons')
+            .content.querySelector('[data-notification-icon="' + options.level + '"]');
+        var current = root.querySelector('[data-notification-icon]');
+        if (icon && current) current.replaceWith(icon.cloneNode(true));
+
+        root.querySelector('[data-notification-title]').textContent = options.title || '';
+        root.querySelector('[data-notification-message]').textContent = options.message || '';
+
+        var actions = root.querySelector('[data-notification-actions]');
+        if (!actions) return;
+        actions.innerHTML = '';
+        (options.actions || []).slice(0, MAX_ACTIONS).forEach(function(action, i) {
+            var button = document.createElement('button');
+            button.type = 'button';
+            button.className = 'rounded-md bg-white text-sm font-medium focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2 '
+                + (i === 0 ? 'text-indigo-600 hover:text-indigo-500' : 'text-gray-700 hover:text-gray-500');
+            button.textContent = action.label;
+            button.setAttribute('hx-post', action.hxPost);
+            button.setAttribute('hx-swap', 'none');
+            button.setAttribute('data-notification-action', '');
+            actions.appendChild(button);
+        });
+        actions.classList.toggle('hidden', !actions.children.length);
+        actions.classList.toggle('flex', !!actions.children.length);
+    }
+
+    function startTimer(notification, timeout) {
+        clearTimeout(notification.ycNotificationTimer);
+        var progress = notification.querySelector('[data-notification-progress]');
+        if (progress) progress.classList.toggle('hidden', !timeout);
+        if (!timeout) return;
+
+        if (progress) {
+            progress.style.transition = 'none';
+            progress.style.width = '100%';
+            progress.getBoundingClientRect();
+            progress.style.transition = 'width ' + timeout + 'ms linear';
+            progress.style.width = '0%';
+        }
+        notification.ycNotificationTimer = setTimeout(function() { dismiss(notification); }, timeout);
+    }
+
+    document.addEventListener('click', function(event) {
+        var action = event.target.closest('[data-notification-action]');
+        var notification = action && action.closest('[data-notification-content] > *');
+        if (notification) setTimeout(function() { dismiss(notification); });
+    });
+
+    function findById(id) {
+        return Array.prototype.find.call(region().children, function(notification) {
+            return notification.getAttribute('data-notification-id') === id;
+        });
+    }
+
+    YcControls.notify = function(options) {
+        var existing = options.id && findById(String(options.id));
+        if (existing) {
+            fill(existing, options);
+            htmx.process(existing);
+            startTimer(existing, options.timeout === undefined ? DEFAULT_TIMEOUT : options.timeout);
+            return;
+        }
+
+        var template = document.getElementById('tpl-notification').cloneNode(true);
+        fill(template.content, options);
+
+        var previous = region().lastElementChild;
+        YcControls.showNotificationWithTemplate(template);
+        // Newest is last until the region reorders it.
+        var notification = region().lastElementChild;
+        if (!notification || notification === previous) return;
+
+        if (options.id) notification.setAttribute('data-notification-id', options.id);
+        htmx.process(notification);
+        startTimer(notification, options.timeout === undefined ? DEFAULT_TIMEOUT : options.timeout);
+    };
+});
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_live_region_options() {
+        let html = html! {
+            <NotificationLiveRegion
+                position=NotificationPosition::BottomLeft
+                max_visible=Some(3)
+                order=NotificationOrder::NewestFirst
+            />
+        };
+        // Attribute values are escaped by `html!`.
+        let html = html
+            .replace("&#x2D;", "-")
+            .replace("&#x20;", " ")
+            .replace("&#x3A;", ":");
+
+        assert!(html.contains("data-notification-order=\"newest-first\""));
+        assert!(html.contains("data-notification-max-visible=\"3\""));
+        assert!(html.contains("space-y-4 sm:items-start"));
+    }
+
+    #[tokio::test]
+    async fn test_flashes_map_to_levels() {
+        let html = html! {
+            <NotificationPresenter call=NotificationCall::from_flash(axum_flash::Level::Warning, "Low on fuel".into()) />
+        };
+
+        assert!(html.contains(r#""level":"warning","message":"Low on fuel""#));
+    }
+
+    #[tokio::test]
+    async fn test_standard_calls_use_notify() {
+        let calls = [
+            NotificationCall::Success("Saved!".into()),
+            NotificationCall::Error("Failed!".into()),
+            NotificationCall::Info("Heads up".into(), "News".into()),
+        ];
+
+        for call in calls {
+            let html = call.render().await.unwrap();
+            assert!(html.contains("notify("), "{}", html);
+            assert!(!html.contains("Notification("), "{}", html);
+        }
+    }
+
+    #[test]
+    fn test_to_hx_trigger() {
+        let trigger = NotificationCall::Success("Saved!".into()).to_hx_trigger();
+
+        assert_eq!(
+            trigger.unwrap().to_header_value(),
+            r#"{"notify":{"actions":[],"id":null,"level":"success","message":"Saved!","timeout":5000,"title":"Success"}}"#
+        );
+        assert_eq!(NotificationCall::Template("".into()).to_hx_trigger(), None);
+    }
+
+    #[tokio::test]
+    async fn test_empty_template_is_left_out() {
+        let result = NotificationCall::Template(" ".into()).render().await;
+        assert!(matches!(result, Err(NotificationError::EmptyTemplate)));
+
+        let html = html! { <NotificationPresenter call=NotificationCall::Template("".into()) /> };
+        assert_eq!(html, "");
+    }
+
+    #[test]
+    fn test_notification_id() {
+        let notification = Notification::new(NotificationLevel::Info, "50%…").id("export-42");
+
+        assert_eq!(notification.to_json()["id"], "export-42");
+    }
+
+    #[test]
+    fn test_notification_options() {
+        let notification = Notification::new(NotificationLevel::Success, "Deleted Foo.")
+            .sticky()
+            .action("Undo", "/foo/undo")
+            .action("View", "/foo")
+            .action("Ignored", "/ignored");
+
+        assert_eq!(
+            notification.to_json(),
+            json!({
+                "id": null,
+                "level": "success",
+                "title": "Success",
+                "message": "Deleted Foo.",
+                "timeout": null,
+                "actions": [
+                    { "label": "Undo", "hxPost": "/foo/undo" },
+                    { "label": "View", "hxPost": "/foo" },
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::Request,
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_flash::{IncomingFlashes, Level};
+use rscx::html;
+use serde_json::Value;
+
+use super::{NotificationCall, NotificationFlashes, NOTIFY_EVENT};
+use crate::server::hx_trigger::{HxTrigger, HX_TRIGGER};
+
+// Response headers making htmx navigate, flashes wait for the next page.
+const HX_NAVIGATION_HEADERS: [&str; 3] = ["HX-Redirect", "HX-Location", "HX-Refresh"];
+
+// Cookie axum_flash keeps the flashes in.
+const FLASH_COOKIE: &str = "axum-flash=";
+
+// Pages up to this size are buffered to add the flashes, larger ones are passed on.
+const MAX_PAGE_SIZE: u64 = 2 * 1024 * 1024;
+
+/**
+ * flash_notifications
+ *
+ * Middleware showing pending axum_flash messages as notifications on the next
+ * response that stays on a page: with the `notify` event of the `HX-Trigger`
+ * header for htmx requests, and in the `NotificationLiveRegion` of full pages.
+ * Redirects keep them for the page redirected to, as do streamed pages and
+ * pages over 2 MiB, which are passed on untouched.
+ *
+ * Use it instead of rendering `NotificationFlashes` in the layout, e.g.
+ * `router.layer(middleware::from_fn_with_state(state, flash_notifications))`.
+ */
+pub async fn flash_notifications(
+    flashes: IncomingFlashes,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_htmx_request = request.headers().contains_key("HX-Request");
+    let response = next.run(request).await;
+
+    if flashes.is_empty() || !can_deliver(&response) {
+        return response;
+    }
+
+    if is_htmx_request {
+        let trigger = flash_trigger(&flashes, response.headers());
+        return (flashes, trigger, response).into_response();
+    }
+
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    if !is_html {
+        return response;
+    }
+
+    if !can_buffer(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let page = match to_bytes(body, MAX_PAGE_SIZE as usize).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::error!(%error, "flash_notifications could not read the page");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let markup = html! { <NotificationFlashes flashes=flashes.clone() /> };
+
+    match std::str::from_utf8(&page)
+        .ok()
+        .and_then(|page| insert_before_body_end(page, &markup))
+    {
+        Some(page) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            (flashes, Response::from_parts(parts, Body::from(page))).into_response()
+        }
+        None => Response::from_parts(parts, Body::from(page)),
+    }
+}
+
+// Streamed bodies have no exact size, they'd have to be read to the end.
+fn can_buffer(response: &Response) -> bool {
+    response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|size| size <= MAX_PAGE_SIZE)
+}
+
+fn can_deliver(response: &Response) -> bool {
+    let headers = response.headers();
+    let navigates = response.status().is_redirection()
+        || HX_NAVIGATION_HEADERS
+            .iter()
+            .any(|name| headers.contains_key(*name));
+    // New flashes replace the pending ones anyway.
+    let sets_flashes = headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.starts_with(FLASH_COOKIE));
+
+    !navigates && !sets_flashes
+}
+
+// All notifications go into one `notify` event, next to any the handler triggers.
+fn flash_trigger<'a>(
+    flashes: impl IntoIterator<Item = (Level, &'a str)>,
+    headers: &HeaderMap,
+) -> HxTrigger {
+    let triggered = headers
+        .get(HX_TRIGGER)
+        .and_then(|value| value.to_str().ok())
+        .map(HxTrigger::parse)
+        .unwrap_or_default();
+
+    let mut notifications = match triggered.get(NOTIFY_EVENT) {
+        Some(Value::Array(notifications)) => notifications.clone(),
+        Some(Value::Null) | None => vec![],
+        Some(notification) => vec![notification.clone()],
+    };
+    notifications.extend(flashes.into_iter().filter_map(|(level, message)| {
+        NotificationCall::from_flash(level, message.into())
+            .to_notification()
+            .map(|notification| notification.to_json())
+    }));
+
+    HxTrigger::new().event_with(NOTIFY_EVENT, Value::Array(notifications))
+}
+
+fn insert_before_body_end(page: &str, markup: &str) -> Option<String> {
+    page.rfind("</body>")
+        .map(|end| format!("{}{}{}", &page[..end], markup, &page[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_flash_trigger_keeps_triggered_notification() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HX_TRIGGER,
+            r#"{"notify":{"message":"Saved!"},"saved":null}"#.parse().unwrap(),
+        );
+
+        let trigger = flash_trigger([(Level::Warning, "Low on fuel")], &headers);
+        let notifications = trigger.get(NOTIFY_EVENT).unwrap().as_array().unwrap();
+
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0]["message"], "Saved!");
+        assert_eq!(notifications[1]["level"], "warning");
+    }
+
+    #[test]
+    fn test_redirects_keep_flashes() {
+        let redirect = (StatusCode::SEE_OTHER, [(header::LOCATION, "/")]).into_response();
+        let hx_redirect = ([("HX-Redirect", "/")], "").into_response();
+
+        assert!(!can_deliver(&redirect));
+        assert!(!can_deliver(&hx_redirect));
+        assert!(can_deliver(&"Ok".into_response()));
+    }
+
+    #[test]
+    fn test_only_small_complete_pages_are_buffered() {
+        let stream = futures_util::stream::iter([Ok::<_, std::io::Error>("<body></body>")]);
+        let streamed = Response::new(Body::from_stream(stream));
+        let large = Response::new(Body::from(vec![b' '; MAX_PAGE_SIZE as usize + 1]));
+
+        assert!(can_buffer(&"<body></body>".into_response()));
+        assert!(!can_buffer(&streamed));
+        assert!(!can_buffer(&large));
+    }
+
+    #[test]
+    fn test_insert_before_body_end() {
+        assert_eq!(
+            insert_before_body_end("<html><body>Page</body></html>", "<script></script>"),
+            Some("<html><body>Page<script></script></body></html>".into())
+        );
+        assert_eq!(insert_before_body_end("<p>Partial</p>", ""), None);
+    }
+}
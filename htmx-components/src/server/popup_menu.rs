@@ -1,20 +1,40 @@
+use std::collections::HashMap;
+
 use rscx::{component, html, props, CollectFragment};
 use typed_builder::TypedBuilder;
 
 use rscx_web_macros::*;
 
 use super::attrs::Attrs;
+use super::confirm::{with_confirm, Confirm};
 use super::html_element::HtmlElement;
 use super::opt_attrs::opt_attrs;
-use super::transition::Transition;
+use super::transition::{Transition, TransitionDuration, TransitionPreset};
 use super::yc_control::Toggle;
 
 pub enum MenuSize {
     Small,
     Medium,
+    Large,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum MenuAlign {
+    Left,
+    #[default]
+    Right,
 }
 
-#[derive(TypedBuilder)]
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum MenuPlacement {
+    #[default]
+    Below,
+    Above,
+    // Below, unless there is no room left in the viewport.
+    Auto,
+}
+
+#[derive(Clone, TypedBuilder)]
 pub struct MenuLink {
     #[builder(setter(into))]
     label: String,
@@ -22,17 +42,51 @@ pub struct MenuLink {
     #[builder(setter(into), default="".into())]
     sr_suffix: String,
 
+    // Leading icon, e.g. an svg.
+    #[builder(setter(into), default)]
+    icon: String,
+
+    // Keyboard shortcut hint, e.g. "⌘K".
+    #[builder(setter(into), default)]
+    shortcut: String,
+
+    #[builder(default = false)]
+    disabled: bool,
+
+    #[builder(default = false)]
+    destructive: bool,
+
     #[builder(default=Attrs::default())]
     attrs: Attrs,
 }
 
 impl From<(String, String)> for MenuLink {
     fn from((label, href): (String, String)) -> Self {
-        Self {
-            label,
-            sr_suffix: "".into(),
-            attrs: Attrs::with("href", href),
-        }
+        MenuLink::builder()
+            .label(label)
+            .attrs(Attrs::with("href", href))
+            .build()
+    }
+}
+
+#[derive(Clone)]
+pub enum MenuEntry {
+    Link(MenuLink),
+    // Entries under a heading.
+    Group {
+        label: String,
+        entries: Vec<MenuEntry>,
+    },
+    Divider,
+    Submenu {
+        label: String,
+        entries: Vec<MenuEntry>,
+    },
+}
+
+impl From<MenuLink> for MenuEntry {
+    fn from(link: MenuLink) -> Self {
+        MenuEntry::Link(link)
     }
 }
 
@@ -51,21 +105,42 @@ pub struct PopupMenuProps {
     #[builder(default=MenuSize::Medium)]
     size: MenuSize,
 
+    #[builder(default)]
+    align: MenuAlign,
+
+    #[builder(default)]
+    placement: MenuPlacement,
+
     children: String,
 }
 
+/**
+ * PopupMenu
+ *
+ * Menu button following the WAI-ARIA menu button pattern, see `POPUP_MENU_SCRIPT`
+ * for the keyboard support. Items should have `role="menuitem"`, like `MenuItem`
+ * and `Menu` render them.
+ */
 #[component]
 pub fn PopupMenu(props: PopupMenuProps) -> String {
+    let button_id = format!("{}-button", &props.id);
+    let menu_id = format!("{}-menu", &props.id);
+
     html! {
-        <Toggle class=format!("relative {}", props.class).trim()>
+        <Toggle
+            class=format!("relative {}", props.class).trim()
+            attrs=Attrs::with("data-popup-menu", "true".into())
+        >
             <div>
                 <button
                     type="button"
-                    id=format!("{}-button", &props.id)
+                    id=button_id.clone()
                     class=format!("relative {}", props.button_class).trim()
                     aria-expanded="false"
-                    aria-haspopup="true"
+                    aria-haspopup="menu"
+                    aria-controls=menu_id.clone()
                     data-toggle-action="click"
+                    data-menu-button
                 >
                     <span class="absolute -inset-1.5"></span>
                     <span class="sr-only">Open menu</span>
@@ -77,19 +152,30 @@ pub fn PopupMenu(props: PopupMenuProps) -> String {
                     let m_width = match props.size {
                         MenuSize::Small => "w-32".to_string(),
                         MenuSize::Medium => "w-48".to_string(),
+                        MenuSize::Large => "w-64".to_string(),
+                    };
+                    let (side, origin) = match props.align {
+                        MenuAlign::Left => ("left-0", "left"),
+                        MenuAlign::Right => ("right-0", "right"),
+                    };
+                    let (offset, origin) = match props.placement {
+                        MenuPlacement::Above => ("bottom-full mb-2", format!("origin-bottom-{}", origin)),
+                        _ => ("mt-2", format!("origin-top-{}", origin)),
                     };
-                    format!("absolute right-0 z-10 mt-2 {} origin-top-right rounded-md bg-white py-1 shadow-lg ring-1 ring-black ring-opacity-5 focus:outline-none", m_width)
+                    format!("absolute {} z-10 {} {} {} rounded-md bg-white py-1 shadow-lg ring-1 ring-black ring-opacity-5 focus:outline-none", side, offset, m_width, origin)
                 }
+                id=menu_id
                 role="menu"
                 aria_orientation="vertical"
-                aria_labelledby=format!("{}-button", &props.id)
+                aria_labelledby=button_id
                 tabindex="-1"
-                enter="transition ease-out duration-200"
-                enter_from="transform opacity-0 scale-95"
-                enter_to="transform opacity-100 scale-100"
-                leave="transition ease-in duration-75"
-                leave_from="transform opacity-100 scale-100"
-                leave_to="transform opacity-0 scale-95"
+                attrs=Attrs::with("data-menu", "true".into()).set_if(
+                    "data-menu-placement",
+                    "auto".into(),
+                    props.placement == MenuPlacement::Auto,
+                )
+                preset=TransitionPreset::Scale
+                duration=TransitionDuration::Fast
             >
                 {props.children}
             </Transition>
@@ -104,6 +190,9 @@ pub struct MenuItemProps {
 
     #[builder(setter(into), default)]
     sr_suffix: String,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
 }
 
 #[component]
@@ -117,7 +206,7 @@ pub fn MenuItem(props: MenuItemProps) -> String {
             }
             role="menuitem"
             tabindex="-1"
-            attrs=spread_attrs!(props | omit(class))
+            attrs=with_confirm(spread_attrs!(props | omit(class)), &props.confirm)
         >
             {props.title}
             <span class="sr-only">{props.sr_suffix}</span>
@@ -125,43 +214,452 @@ pub fn MenuItem(props: MenuItemProps) -> String {
     }
 }
 
+/**
+ * Menu
+ *
+ * Items of a `PopupMenu` from a list of entries: links, groups under a
+ * heading, dividers and submenus. `links` are rendered before the `entries`.
+ */
 #[props]
 pub struct MenuProps {
     #[builder(setter(into))]
     id: String,
 
+    #[builder(default)]
     links: Vec<MenuLink>,
+
+    #[builder(default)]
+    entries: Vec<MenuEntry>,
 }
 
 #[component]
 pub fn Menu(props: MenuProps) -> String {
-    #[allow(unused_braces)]
-    props
+    let entries = props
         .links
+        .into_iter()
+        .map(MenuEntry::from)
+        .chain(props.entries)
+        .collect();
+
+    menu_entries(&props.id, entries)
+}
+
+fn menu_entries(id: &str, entries: Vec<MenuEntry>) -> String {
+    entries
         .into_iter()
         .enumerate()
-        .map(
-            |(
-                i,
-                MenuLink {
-                    label,
-                    sr_suffix,
-                    attrs,
+        .map(|(i, entry)| {
+            let id = format!("{}-item-{}", id, i);
+            match entry {
+                MenuEntry::Link(link) => menu_link(&id, link),
+                MenuEntry::Group { label, entries } => html! {
+                    <div role="group" aria-labelledby=format!("{}-label", &id)>
+                        <p
+                            id=format!("{}-label", &id)
+                            role="presentation"
+                            class="px-4 pb-1 pt-2 text-xs font-semibold uppercase tracking-wide text-gray-500"
+                        >
+                            {label}
+                        </p>
+                        {menu_entries(&id, entries)}
+                    </div>
                 },
-            )| {
-                html! {
-                    <a
-                        class="block px-4 py-2 text-sm text-gray-700 cursor-pointer hover:bg-gray-50"
-                        role="menuitem"
-                        tabindex="-1"
-                        id={format!("{}-item-{}", &props.id, i)}
-                        {opt_attrs(attrs.to_hashmap())}
-                    >
-                        {label}
-                        <span class="sr-only">", "{sr_suffix}</span>
-                    </a>
-                }
-            },
-        )
+                MenuEntry::Divider => html! {
+                    <div role="separator" class="my-1 h-px bg-gray-100"></div>
+                },
+                MenuEntry::Submenu { label, entries } => html! {
+                    <div class="relative" data-submenu>
+                        <a
+                            id=id.clone()
+                            class="flex cursor-pointer items-center px-4 py-2 text-sm text-gray-700 hover:bg-gray-50"
+                            role="menuitem"
+                            tabindex="-1"
+                            aria-haspopup="menu"
+                            aria-expanded="false"
+                            aria-controls=format!("{}-menu", &id)
+                            data-submenu-button
+                        >
+                            {label}
+                            <svg class="ml-auto h-5 w-5 text-gray-400" viewBox="0 0 20 20" fill="currentColor" aria-hidden="true">
+                                <path fill-rule="evenodd" d="M7.21 14.77a.75.75 0 01.02-1.06L11.168 10 7.23 6.29a.75.75 0 111.04-1.08l4.5 4.25a.75.75 0 010 1.08l-4.5 4.25a.75.75 0 01-1.06-.02z" clip-rule="evenodd" />
+                            </svg>
+                        </a>
+                        <div
+                            id=format!("{}-menu", &id)
+                            class="absolute left-full top-0 z-10 -mt-1 hidden w-48 rounded-md bg-white py-1 shadow-lg ring-1 ring-black ring-opacity-5 focus:outline-none"
+                            role="menu"
+                            aria-labelledby=id.clone()
+                            tabindex="-1"
+                            data-submenu-menu
+                        >
+                            {menu_entries(&id, entries)}
+                        </div>
+                    </div>
+                },
+            }
+        })
         .collect_fragment()
 }
+
+#[allow(unused_braces)]
+fn menu_link(id: &str, link: MenuLink) -> String {
+    let MenuLink {
+        label,
+        sr_suffix,
+        icon,
+        shortcut,
+        disabled,
+        destructive,
+        attrs,
+    } = link;
+
+    let color = if destructive {
+        "text-red-700 hover:bg-red-50"
+    } else {
+        "text-gray-700 hover:bg-gray-50"
+    };
+    let state = if disabled {
+        "cursor-not-allowed opacity-50 pointer-events-none"
+    } else {
+        "cursor-pointer"
+    };
+    // Disabled links don't go anywhere, but keep their other attrs.
+    let attrs = match disabled {
+        true => Attrs::from(
+            attrs
+                .to_hashmap()
+                .into_iter()
+                .filter(|(key, _)| {
+                    !matches!(*key, "href" | "onclick")
+                        && !key.starts_with("hx-")
+                        && !key.starts_with("data-hx-")
+                })
+                .collect::<HashMap<_, _>>(),
+        )
+        .set("aria-disabled", "true".into()),
+        false => attrs,
+    };
+    let attrs = opt_attrs(attrs.to_hashmap());
+
+    html! {
+        <a
+            class=format!("group flex items-center px-4 py-2 text-sm {} {}", color, state)
+            role="menuitem"
+            tabindex="-1"
+            id={id}
+            {attrs}
+        >
+            {
+                if icon.is_empty() {
+                    String::new()
+                } else {
+                    html! { <span class="mr-3 flex h-5 w-5 flex-shrink-0 items-center" aria-hidden="true">{icon}</span> }
+                }
+            }
+            {label}
+            <span class="sr-only">", "{sr_suffix}</span>
+            {
+                if shortcut.is_empty() {
+                    String::new()
+                } else {
+                    html! { <kbd class="ml-auto pl-3 font-sans text-xs text-gray-400">{shortcut}</kbd> }
+                }
+            }
+        </a>
+    }
+}
+
+// Keyboard support and ARIA state of `PopupMenu`s, following the WAI-ARIA menu
+// button pattern. Opening and closing stays with the `Toggle` control:
+// - Enter, Space and ArrowDown on the button open the menu on its first item,
+//   ArrowUp on its last.
+// - ArrowUp/ArrowDown move between items, Home/End go to the first/last item,
+//   typing a character goes to the next item starting with it.
+// - Escape closes the menu and returns focus to the button, Tab closes it.
+// - ArrowRight, Enter and Space open a submenu, ArrowLeft and Escape close it.
+// Menus with `data-menu-placement="auto"` open upwards near the bottom of the viewport.
+pub(crate) const POPUP_MENU_SCRIPT: &str = r#"
+(function() {
+    var pendingFocus = null;
+    var typeahead = '';
+    var typeaheadTimer;
+
+    function parts(el) {
+        var root = el.closest('[data-popup-menu]');
+        return root && {
+            button: root.querySelector('[data-menu-button]'),
+            menu: root.querySelector('[data-menu]')
+        };
+    }
+
+    // Enabled items of `menu`, without the ones of its submenus.
+    function items(menu) {
+        return Array.prototype.filter.call(menu.querySelectorAll('[role~="menuitem"]'), function(item) {
+            return item.getAttribute('aria-disabled') !== 'true' && item.parentNode.closest('[role="menu"]') === menu;
+        });
+    }
+
+    function submenuOf(button) {
+        return button.parentNode.querySelector('[data-submenu-menu]');
+    }
+
+    function openSubmenu(button, focus) {
+        var submenu = submenuOf(button);
+        submenu.classList.remove('hidden');
+        button.setAttribute('aria-expanded', 'true');
+        if (focus) focusItem(submenu, 'first');
+    }
+
+    function closeSubmenus(within) {
+        within.querySelectorAll('[data-submenu-button][aria-expanded="true"]').forEach(function(button) {
+            submenuOf(button).classList.add('hidden');
+            button.setAttribute('aria-expanded', 'false');
+        });
+    }
+
+    function place(menu) {
+        if (menu.getAttribute('data-menu-placement') !== 'auto') return;
+        ['bottom-full', 'mb-2'].forEach(function(name) { menu.classList.remove(name); });
+        menu.classList.add('mt-2');
+
+        var rect = menu.getBoundingClientRect();
+        var buttonTop = parts(menu).button.getBoundingClientRect().top;
+        if (rect.bottom > window.innerHeight && buttonTop > rect.height) {
+            menu.classList.remove('mt-2');
+            menu.classList.add('bottom-full', 'mb-2');
+        }
+    }
+
+    function isOpen(menu) {
+        return !menu.classList.contains('hidden');
+    }
+
+    function focusItem(menu, which) {
+        var all = items(menu);
+        if (!all.length) return menu.focus();
+        var current = all.indexOf(document.activeElement);
+        var index = {
+            first: 0,
+            last: all.length - 1,
+            next: (current + 1) % all.length,
+            previous: (current - 1 + all.length) % all.length
+        }[which];
+        all[index].focus();
+    }
+
+    function focusByText(menu, key) {
+        clearTimeout(typeaheadTimer);
+        typeahead += key.toLowerCase();
+        typeaheadTimer = setTimeout(function() { typeahead = ''; }, 500);
+
+        var all = items(menu);
+        var start = all.indexOf(document.activeElement) + (typeahead.length === 1 ? 1 : 0);
+        for (var i = 0; i < all.length; i++) {
+            var item = all[(start + i) % all.length];
+            if (item.textContent.trim().toLowerCase().indexOf(typeahead) === 0) return item.focus();
+        }
+    }
+
+    // The Toggle shows and hides the menu, keep ARIA state and focus in sync.
+    new MutationObserver(function(mutations) {
+        mutations.forEach(function(mutation) {
+            var menu = mutation.target;
+            if (!menu.matches || !menu.matches('[data-menu]')) return;
+            var button = parts(menu).button;
+            var open = isOpen(menu);
+            if (button.getAttribute('aria-expanded') === String(open)) return;
+
+            button.setAttribute('aria-expanded', String(open));
+            if (open) {
+                place(menu);
+                focusItem(menu, pendingFocus || 'first');
+            } else {
+                closeSubmenus(menu);
+                if (menu.contains(document.activeElement)) button.focus();
+            }
+            pendingFocus = null;
+        });
+    }).observe(document, { attributes: true, attributeFilter: ['class'], subtree: true });
+
+    document.addEventListener('keydown', function(event) {
+        var target = event.target;
+        var menu = target.closest && parts(target);
+        if (!menu || !menu.menu) return;
+        var button = menu.button;
+        menu = menu.menu;
+
+        if (target === button) {
+            if (event.key === 'ArrowDown' || event.key === 'ArrowUp') {
+                event.preventDefault();
+                pendingFocus = event.key === 'ArrowDown' ? 'first' : 'last';
+                if (isOpen(menu)) focusItem(menu, pendingFocus); else button.click();
+            } else if (event.key === 'Enter' || event.key === ' ') {
+                pendingFocus = 'first';
+            }
+            return;
+        }
+
+        if (!menu.contains(target)) return;
+        var current = target.closest('[role="menu"]');
+        var isSubmenuButton = target.matches('[data-submenu-button]');
+        var parentButton = current.matches('[data-submenu-menu]') && current.parentNode.querySelector('[data-submenu-button]');
+
+        switch (event.key) {
+            case 'ArrowDown': focusItem(current, 'next'); break;
+            case 'ArrowUp': focusItem(current, 'previous'); break;
+            case 'Home': focusItem(current, 'first'); break;
+            case 'End': focusItem(current, 'last'); break;
+            case 'ArrowRight':
+                if (!isSubmenuButton) return;
+                openSubmenu(target, true);
+                break;
+            case 'ArrowLeft':
+                if (!parentButton) return;
+                closeSubmenus(parentButton.parentNode);
+                parentButton.focus();
+                break;
+            case 'Escape':
+                if (parentButton) {
+                    closeSubmenus(parentButton.parentNode);
+                    parentButton.focus();
+                    break;
+                }
+                button.focus();
+                button.click();
+                break;
+            case 'Tab':
+                button.click();
+                return;
+            // Items without href (e.g. `hx-get` only) don't activate on Enter by themselves.
+            case 'Enter':
+            case ' ':
+                if (isSubmenuButton) openSubmenu(target, true); else target.click();
+                break;
+            default:
+                if (event.key.length === 1 && !event.ctrlKey && !event.metaKey && !event.altKey) {
+                    focusByText(current, event.key);
+                    break;
+                }
+                return;
+        }
+        event.preventDefault();
+        event.stopPropagation();
+    }, true);
+
+    // Submenus also open on hover and click.
+    document.addEventListener('mouseover', function(event) {
+        var entry = event.target.closest && event.target.closest('[data-submenu]');
+        var menu = event.target.closest && event.target.closest('[role="menu"]');
+        if (!menu) return;
+        menu.querySelectorAll(':scope > [data-submenu]').forEach(function(other) {
+            if (other !== entry) closeSubmenus(other);
+        });
+        if (entry && entry.parentNode === menu) openSubmenu(entry.querySelector('[data-submenu-button]'), false);
+    });
+
+    document.addEventListener('click', function(event) {
+        var button = event.target.closest && event.target.closest('[data-submenu-button]');
+        if (button) openSubmenu(button, false);
+    });
+})();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Value of attribute `name` of the tag containing `marker`.
+    fn attr<'a>(html: &'a str, marker: &str, name: &str) -> &'a str {
+        let at = html.find(marker).unwrap();
+        let tag = &html[html[..at].rfind('<').unwrap()..];
+        let tag = &tag[..tag.find('>').unwrap()];
+        let value = &tag[tag.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3..];
+        &value[..value.find('"').unwrap()]
+    }
+
+    #[tokio::test]
+    async fn test_menu_button_aria() {
+        let html = html! {
+            <PopupMenu id="user" button_content="Open".into()>
+                <MenuItem title="Profile" />
+            </PopupMenu>
+        }
+        .replace("&#x2D;", "-");
+
+        let button = "data-menu-button";
+        assert_eq!(attr(&html, button, "aria-haspopup"), "menu");
+        assert_eq!(attr(&html, button, "aria-expanded"), "false");
+        assert_eq!(attr(&html, button, "aria-controls"), "user-menu");
+        assert_eq!(attr(&html, button, "id"), "user-button");
+
+        let menu = "role=\"menu\"";
+        assert_eq!(attr(&html, menu, "id"), "user-menu");
+        assert_eq!(attr(&html, menu, "aria-labelledby"), "user-button");
+        assert_eq!(attr(&html, menu, "tabindex"), "-1");
+
+        assert!(html.contains("role=\"menuitem\""));
+    }
+
+    #[tokio::test]
+    async fn test_menu_links() {
+        let html = html! {
+            <Menu
+                id="user"
+                links=vec![("Profile".to_string(), "/profile".to_string()).into()]
+                entries=vec![MenuEntry::Divider]
+            />
+        }
+        .replace("&#x2D;", "-");
+
+        assert!(html.contains("id=\"user-item-0\""));
+        assert!(html.contains("Profile"));
+        assert!(html.find("Profile").unwrap() < html.find("role=\"separator\"").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_menu_entries() {
+        let html = html! {
+            <Menu
+                id="actions"
+                entries=vec![
+                    MenuEntry::Group {
+                        label: "Edit".into(),
+                        entries: vec![
+                            MenuLink::builder().label("Rename").shortcut("R").build().into(),
+                            MenuLink::builder()
+                                .label("Move")
+                                .disabled(true)
+                                .attrs(
+                                    Attrs::with("href", "/move".into())
+                                        .set("hx-post", "/move".into())
+                                        .set("data-hx-get", "/move/preview".into())
+                                        .set("data-testid", "move-item".into()),
+                                )
+                                .build()
+                                .into(),
+                        ],
+                    },
+                    MenuEntry::Divider,
+                    MenuEntry::Submenu {
+                        label: "Share".into(),
+                        entries: vec![MenuEntry::Link(
+                            ("Email".to_string(), "/share/email".to_string()).into(),
+                        )],
+                    },
+                    MenuLink::builder().label("Delete").destructive(true).build().into(),
+                ]
+            />
+        }
+        .replace("&#x2D;", "-");
+
+        assert!(html.contains("role=\"group\""));
+        assert!(html.contains("role=\"separator\""));
+        assert!(html.contains("<kbd"));
+        assert!(html.contains("aria-disabled=\"true\""));
+        assert!(!html.contains("/move"));
+        assert!(!html.contains("hx-get"));
+        assert!(html.contains("data-testid=\"move-item\""));
+        assert!(html.contains("data-submenu-menu"));
+        assert!(html.contains("text-red-700"));
+    }
+}
@@ -1,12 +1,22 @@
-use rscx::{component, html, props, CollectFragment};
+use rscx::{component, html, html_escape, props, CollectFragment, CollectFragmentAsync};
 
+use super::attrs::Attrs;
+use super::button::{ButtonSize, SecondaryButton};
 use super::html_element::HtmlElement;
 
+use super::confirm::with_confirm;
+pub use super::confirm::Confirm;
+
 use rscx_web_macros::*;
 
+pub mod data_table;
+pub mod export;
+pub mod query;
+
 pub enum TableHeading {
     Title(String),
     Empty(String),
+    SelectAll(String),
 }
 
 impl TableHeading {
@@ -16,6 +26,9 @@ impl TableHeading {
     pub fn empty(sr_only_text: impl Into<String>) -> TableHeading {
         TableHeading::Empty(sr_only_text.into())
     }
+    pub fn select_all(sr_only_text: impl Into<String>) -> TableHeading {
+        TableHeading::SelectAll(sr_only_text.into())
+    }
 }
 
 pub type TableHeadings = Vec<TableHeading>;
@@ -97,6 +110,16 @@ fn TableHeadingsRow(headings: TableHeadings) -> String {
                             <span class="sr-only">{sr_only_text}</span>
                         </th>
                     },
+                    TableHeading::SelectAll(sr_only_text) => html! {
+                        <th scope="col" class="relative px-7 sm:w-12 sm:px-6">
+                            <input
+                                type="checkbox"
+                                class=SELECT_CHECKBOX_CLASS
+                                aria-label=sr_only_text.as_str()
+                                data-bulk-select-all
+                            />
+                        </th>
+                    },
                 }
             }).collect_fragment()}
             </tr>
@@ -110,7 +133,7 @@ fn TableBody(body: Vec<String>) -> String {
         <tbody class="divide-y divide-gray-200 bg-white">
             {
                 body.iter().map(|row| html! {
-                    <tr data-loading-states>{row}</tr>
+                    <tr class="has-[:checked]:bg-gray-50" data-loading-states>{row}</tr>
                 })
                 .collect_fragment()
             }
@@ -123,6 +146,9 @@ pub struct ActionLinkProps {
     children: String,
     #[builder(setter(into))]
     sr_text: String,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
 }
 
 #[component]
@@ -131,17 +157,144 @@ pub fn ActionLink(props: ActionLinkProps) -> String {
         <HtmlElement
             tag="a"
             class=format!("cursor-pointer text-indigo-600 hover:text-indigo-900, {}", props.class).trim()
-            attrs=spread_attrs!(props | omit(class))
+            attrs=with_confirm(spread_attrs!(props | omit(class)), &props.confirm)
         >
             {props.children}<span class="sr-only">{props.sr_text}</span>
         </HtmlElement>
     }
 }
 
-#[derive(Clone)]
-pub struct Confirm {
-    pub title: String,
-    pub message: String,
+// #### Row selection and bulk actions. ###############
+
+const SELECT_CHECKBOX_CLASS: &str =
+    "absolute left-4 top-1/2 -mt-2 h-4 w-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-600";
+
+pub(crate) const BULK_SELECT_SCRIPT: &str = r#"
+    (function() {
+        function updateBulkSelect(container) {
+            var rows = container.querySelectorAll("[data-bulk-select-row]");
+            var checked = container.querySelectorAll("[data-bulk-select-row]:checked");
+
+            container.querySelectorAll("[data-bulk-select-count]").forEach(function(count) {
+                count.textContent = checked.length;
+            });
+            container.querySelectorAll("[data-bulk-select-toolbar]").forEach(function(toolbar) {
+                toolbar.classList.toggle("hidden", checked.length === 0);
+                toolbar.classList.toggle("flex", checked.length > 0);
+            });
+            container.querySelectorAll("[data-bulk-select-all]").forEach(function(selectAll) {
+                selectAll.checked = rows.length > 0 && checked.length === rows.length;
+                selectAll.indeterminate = checked.length > 0 && checked.length < rows.length;
+            });
+        }
+
+        document.addEventListener("change", function(e) {
+            var container = e.target.closest("[data-bulk-select]");
+            if (!container) return;
+
+            if (e.target.matches("[data-bulk-select-all]")) {
+                container.querySelectorAll("[data-bulk-select-row]").forEach(function(row) {
+                    row.checked = e.target.checked;
+                });
+            }
+            if (e.target.matches("[data-bulk-select-all], [data-bulk-select-row]")) {
+                updateBulkSelect(container);
+            }
+        });
+
+        // Rows may have been swapped in or out (e.g. after a bulk delete).
+        document.addEventListener("htmx:afterSettle", function() {
+            document.querySelectorAll("[data-bulk-select]").forEach(updateBulkSelect);
+        });
+    }());
+"#;
+
+/**
+ * BulkSelect
+ *
+ * Wraps a `Table` whose rows can be selected (see `TableHeading::select_all` and
+ * `TableRowSelect`) and shows a toolbar of `actions` with the selected count
+ * while any row is selected. Requires an `id`, which the toolbar uses to
+ * `hx-include` the selected rows in its requests.
+ */
+#[html_element]
+pub struct BulkSelectProps {
+    children: String,
+
+    #[builder(setter(into))]
+    actions: String,
+
+    #[builder(setter(into), default = "selected".into())]
+    selected_label: String,
+}
+
+#[component]
+pub fn BulkSelect(props: BulkSelectProps) -> String {
+    html! {
+        <HtmlElement
+            id=props.id.clone()
+            component_name="BulkSelect"
+            attrs=spread_attrs!(props | omit(id)).set("data-bulk-select", "true".into())
+        >
+            <div
+                class="hidden items-center gap-3 bg-white py-3"
+                hx-include=format!("#{} [data-bulk-select-row]", props.id)
+                data-bulk-select-toolbar
+            >
+                <span class="text-sm text-gray-700">
+                    <span class="font-medium" data-bulk-select-count>0</span>
+                    " "{props.selected_label}
+                </span>
+                {props.actions}
+            </div>
+            {props.children}
+        </HtmlElement>
+    }
+}
+
+#[props]
+pub struct TableRowSelectProps {
+    #[builder(setter(into))]
+    value: String,
+
+    #[builder(setter(into), default = "ids".into())]
+    name: String,
+
+    #[builder(setter(into), default = "Select row".into())]
+    sr_text: String,
+}
+
+#[component]
+pub fn TableRowSelect(props: TableRowSelectProps) -> String {
+    html! {
+        <td class="relative px-7 sm:w-12 sm:px-6">
+            <input
+                type="checkbox"
+                class=SELECT_CHECKBOX_CLASS
+                name=props.name
+                value=props.value
+                aria-label=props.sr_text
+                data-bulk-select-row
+            />
+        </td>
+    }
+}
+
+#[html_element]
+pub struct BulkActionButtonProps {
+    children: String,
+
+    #[builder(setter(into), default)]
+    confirm: Option<Confirm>,
+}
+
+#[component]
+pub fn BulkActionButton(props: BulkActionButtonProps) -> String {
+    html! {
+        <SecondaryButton size=ButtonSize::Sm confirm=props.confirm attrs=spread_attrs!(props)>
+            {props.children}
+        </SecondaryButton>
+    }
 }
 
 #[html_element]
@@ -161,9 +314,8 @@ pub fn DeleteActionLink(props: DeleteActionLinkProps) -> String {
     html! {
         <ActionLink
             sr_text=props.sr_text
+            confirm=Some(props.confirm)
             attrs=spread_attrs!(props)
-                .set("hx-confirm", props.confirm.title)
-                .set("data-confirm-message", props.confirm.message)
                 .set_if("data-loading-disable", "true".into(), props.show_loader_on_delete)
         >
             {if props.show_loader_on_delete {
@@ -186,3 +338,299 @@ pub fn TableDataActions(children: String) -> String {
         </div>
     }
 }
+
+// #### Table rows from structs. ###############
+
+/**
+ * TableRow
+ *
+ * A struct that can be presented as a row of a `Table`.
+ * Usually derived with `#[derive(TableRow)]` from `rscx_web_macros`.
+ * `table_cells` returns plain text, which `table_body` escapes.
+ */
+pub trait TableRow {
+    fn table_headings() -> TableHeadings;
+
+    fn table_cells(&self) -> Vec<String>;
+
+    fn table_actions(&self) -> Option<Vec<TableRowAction>> {
+        None
+    }
+}
+
+pub enum TableRowAction {
+    Link {
+        label: String,
+        sr_text: String,
+        attrs: Attrs,
+    },
+    Delete {
+        label: String,
+        sr_text: String,
+        confirm: Confirm,
+        attrs: Attrs,
+    },
+}
+
+impl TableRowAction {
+    pub fn link(label: impl Into<String>, sr_text: impl Into<String>, attrs: Attrs) -> Self {
+        TableRowAction::Link {
+            label: label.into(),
+            sr_text: sr_text.into(),
+            attrs,
+        }
+    }
+    pub fn delete(
+        label: impl Into<String>,
+        sr_text: impl Into<String>,
+        confirm: Confirm,
+        attrs: Attrs,
+    ) -> Self {
+        TableRowAction::Delete {
+            label: label.into(),
+            sr_text: sr_text.into(),
+            confirm,
+            attrs,
+        }
+    }
+}
+
+/**
+ * table_body
+ *
+ * Renders the cells of each row for the `body` of a `Table`, e.g.
+ * `<Table headings=User::table_headings() body=table_body(&users).await />`
+ */
+pub async fn table_body<R: TableRow>(rows: &[R]) -> Vec<String> {
+    let mut body = vec![];
+    for row in rows {
+        body.push(html! {
+            <TableRowCells cells=row.table_cells() actions=row.table_actions() />
+        });
+    }
+    body
+}
+
+#[component]
+fn TableRowCells(cells: Vec<String>, actions: Option<Vec<TableRowAction>>) -> String {
+    let len = cells.len();
+    let has_actions = actions.is_some();
+
+    let cells = cells
+        .into_iter()
+        .enumerate()
+        .map(|(i, cell)| async move {
+            let variant = match i {
+                0 => TDVariant::First,
+                _ if i == len - 1 && !has_actions => TDVariant::LastNonEmptyHeading,
+                _ => TDVariant::Default,
+            };
+
+            html! {
+                <TableData variant=variant>{html_escape::encode_text(&cell)}</TableData>
+            }
+        })
+        .collect_fragment_async()
+        .await;
+
+    let actions = match actions {
+        Some(actions) => html! {
+            <TableData variant=TDVariant::Last>
+                <TableDataActions>
+                    {
+                        actions
+                            .into_iter()
+                            .map(|action| async move {
+                                match action {
+                                    TableRowAction::Link { label, sr_text, attrs } => html! {
+                                        <ActionLink sr_text=sr_text attrs=attrs>{label}</ActionLink>
+                                    },
+                                    TableRowAction::Delete { label, sr_text, confirm, attrs } => html! {
+                                        <DeleteActionLink sr_text=sr_text confirm=confirm attrs=attrs>
+                                            {label}
+                                        </DeleteActionLink>
+                                    },
+                                }
+                            })
+                            .collect_fragment_async()
+                            .await
+                    }
+                </TableDataActions>
+            </TableData>
+        },
+        None => String::new(),
+    };
+
+    html! {
+        {cells}
+        {actions}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::unescape;
+
+    fn format_cents(cents: &u32) -> String {
+        format!("${}.{:02}", cents / 100, cents % 100)
+    }
+
+    fn invoice_actions(invoice: &Invoice) -> Vec<TableRowAction> {
+        vec![TableRowAction::link(
+            "Edit",
+            format!(", {}", invoice.number),
+            Attrs::with("href", format!("/invoices/{}", invoice.id)),
+        )]
+    }
+
+    #[derive(TableRow)]
+    #[table_row(actions = invoice_actions)]
+    struct Invoice {
+        #[table_row(skip)]
+        id: u32,
+        #[table_row(header = "Invoice #")]
+        number: String,
+        #[table_row(format = format_cents)]
+        amount_due: u32,
+    }
+
+    #[derive(TableRow)]
+    struct Customer {
+        name: String,
+        email: String,
+    }
+
+    #[test]
+    fn test_derived_headings() {
+        let headings = Invoice::table_headings();
+
+        assert_eq!(headings.len(), 3);
+        assert!(matches!(&headings[0], TableHeading::Title(title) if title == "Invoice #"));
+        assert!(matches!(&headings[1], TableHeading::Title(title) if title == "Amount due"));
+        assert!(matches!(&headings[2], TableHeading::Empty(sr_text) if sr_text == "Actions"));
+    }
+
+    #[test]
+    fn test_derived_cells() {
+        let invoice = Invoice {
+            id: 7,
+            number: "INV-007".into(),
+            amount_due: 12345,
+        };
+
+        assert_eq!(invoice.table_cells(), vec!["INV-007", "$123.45"]);
+        assert_eq!(
+            invoice.table_actions().map(|actions| actions.len()),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_table_body_places_variants() {
+        let customers = vec![Customer {
+            name: "Mary Sue".into(),
+            email: "mary@example.com".into(),
+        }];
+
+        let body = table_body(&customers).await;
+
+        assert_eq!(
+            body,
+            vec![html! {
+                <TableData variant=TDVariant::First>Mary Sue</TableData>
+                <TableData variant=TDVariant::LastNonEmptyHeading>"mary@example.com"</TableData>
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_table_body_escapes_cells() {
+        let customers = vec![Customer {
+            name: "<script>alert(1)</script>".into(),
+            email: "mary@example.com".into(),
+        }];
+
+        let body = table_body(&customers).await;
+
+        assert!(body[0].contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!body[0].contains("<script>"));
+    }
+
+    #[tokio::test]
+    async fn test_table_body_renders_actions_last() {
+        let invoices = vec![Invoice {
+            id: 7,
+            number: "INV-007".into(),
+            amount_due: 100,
+        }];
+
+        let body = table_body(&invoices).await;
+
+        assert!(body[0].ends_with(&html! {
+            <TableData variant=TDVariant::Last>
+                <TableDataActions>
+                    <ActionLink sr_text=", INV-007" attrs=Attrs::with("href", "/invoices/7".into())>Edit</ActionLink>
+                </TableDataActions>
+            </TableData>
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_select_all_heading() {
+        let html = unescape(html! {
+            <Table
+                headings=vec![TableHeading::select_all("Select all"), TableHeading::title("Name")]
+                body=vec![]
+            />
+        });
+
+        assert!(html.contains("type=\"checkbox\""));
+        assert!(html.contains("aria-label=\"Select all\""));
+        assert!(html.contains("data-bulk-select-all"));
+    }
+
+    #[tokio::test]
+    async fn test_row_select() {
+        let html = unescape(html! {
+            <TableRowSelect value="42" name="invoice_ids" sr_text="Select INV-042" />
+        });
+
+        assert!(html.contains("name=\"invoice_ids\""));
+        assert!(html.contains("value=\"42\""));
+        assert!(html.contains("aria-label=\"Select INV-042\""));
+        assert!(html.contains("data-bulk-select-row"));
+
+        let html = html! { <TableRowSelect value="42" /> };
+        assert!(html.contains("name=\"ids\""));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_action_button_includes_selected_rows() {
+        let html = unescape(html! {
+            <BulkSelect
+                id="invoices"
+                actions=html! {
+                    <BulkActionButton hx_post="/invoices/bulk-delete">Delete</BulkActionButton>
+                }
+            >
+                <Table
+                    headings=vec![TableHeading::select_all("Select all")]
+                    body=vec![html! { <TableRowSelect value="42" /> }]
+                />
+            </BulkSelect>
+        });
+
+        // The button sits in the toolbar, which includes the selected rows in its requests.
+        let toolbar = html
+            .split("data-bulk-select-toolbar")
+            .nth(1)
+            .and_then(|rest| rest.split("<table").next())
+            .unwrap();
+        let toolbar_tag = html.split("data-bulk-select-toolbar").next().unwrap();
+
+        assert!(toolbar_tag.ends_with("hx-include=\"#invoices [data-bulk-select-row]\" "));
+        assert!(toolbar.contains("hx-post=\"/invoices/bulk-delete\""));
+        assert!(html.contains("data-bulk-select=\"true\""));
+    }
+}
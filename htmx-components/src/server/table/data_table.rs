@@ -0,0 +1,508 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use rscx::{component, html, html_escape, props::Props, CollectFragment, CollectFragmentAsync};
+use typed_builder::TypedBuilder;
+
+use super::export::{ExportFormat, ExportScope};
+use super::query::{SortDirection, TableQuery};
+use crate::server::attrs::Attrs;
+use crate::server::button::SecondaryButton;
+
+type CellValueFn<R> = Box<dyn Fn(&R) -> String + Send + Sync>;
+type CellRenderFn<R> =
+    Box<dyn Fn(&R) -> Pin<Box<dyn Future<Output = String> + Send>> + Send + Sync>;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColumnAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl ColumnAlign {
+    fn class(&self) -> &'static str {
+        match self {
+            ColumnAlign::Left => "text-left",
+            ColumnAlign::Center => "text-center",
+            ColumnAlign::Right => "text-right",
+        }
+    }
+}
+
+/**
+ * Column
+ *
+ * Describes how a `DataTable` presents one field of its rows.
+ * `value` is the plain text of the cell, escaped when rendered. Use `cell` to
+ * render richer markup (components, links, badges...) in its place.
+ */
+pub struct Column<R> {
+    header: String,
+    value: CellValueFn<R>,
+    cell: Option<CellRenderFn<R>>,
+    sort_key: Option<String>,
+    key: Option<String>,
+    align: ColumnAlign,
+    width: Option<String>,
+}
+
+impl<R> Column<R> {
+    pub fn new<F>(header: impl Into<String>, value: F) -> Self
+    where
+        F: Fn(&R) -> String + Send + Sync + 'static,
+    {
+        Self {
+            header: header.into(),
+            value: Box::new(value),
+            cell: None,
+            sort_key: None,
+            key: None,
+            align: ColumnAlign::Left,
+            width: None,
+        }
+    }
+
+    pub fn cell<F, Fut>(mut self, render: F) -> Self
+    where
+        F: Fn(&R) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = String> + Send + 'static,
+    {
+        self.cell = Some(Box::new(move |row| Box::pin(render(row))));
+        self
+    }
+
+    pub fn sortable(mut self, sort_key: impl Into<String>) -> Self {
+        self.sort_key = Some(sort_key.into());
+        self
+    }
+
+    // Key of the field in JSON exports, the header by default.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn align(mut self, align: ColumnAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    // Tailwind width class, e.g. "w-32".
+    pub fn width(mut self, width: impl Into<String>) -> Self {
+        self.width = Some(width.into());
+        self
+    }
+
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    pub fn export_key(&self) -> &str {
+        self.key.as_deref().unwrap_or(&self.header)
+    }
+
+    pub fn value(&self, row: &R) -> String {
+        (self.value)(row)
+    }
+
+    async fn render(&self, row: &R) -> String {
+        match &self.cell {
+            Some(render) => render(row).await,
+            None => html_escape::encode_text(&self.value(row)).into_owned(),
+        }
+    }
+}
+
+// Mirrors the padding of the standard `Table` headings and cells.
+fn padding_class(i: usize, len: usize) -> &'static str {
+    match i {
+        0 => "pl-4 pr-3 sm:pl-6",
+        _ if i == len - 1 => "pl-3 pr-4 sm:pr-6",
+        _ => "px-3",
+    }
+}
+
+/**
+ * DataTable
+ *
+ * A table driven by `Column` definitions with sortable headers, a pagination
+ * footer and an empty state. Sorting and paging `hx-get` the `url` with the
+ * updated `TableQuery` params and swap the whole table, so the handler at
+ * `url` should extract a `TableQuery` and render the `DataTable` again.
+ * With `exportable`, the footer links to CSV and JSON downloads of the page
+ * or all rows, which that handler serves when `TableQuery::export` is set.
+ */
+
+// NOTE: rscx's #[props] and #[component] macros don't support generics.
+#[derive(TypedBuilder)]
+#[builder(doc, crate_module_path=::rscx::typed_builder)]
+pub struct DataTableProps<R> {
+    #[builder(setter(into))]
+    id: String,
+
+    columns: Vec<Column<R>>,
+
+    rows: Vec<R>,
+
+    #[builder(default)]
+    query: TableQuery,
+
+    // Total number of rows across all pages.
+    total: usize,
+
+    #[builder(setter(into))]
+    url: String,
+
+    #[builder(setter(into), default = "No results found.".into())]
+    empty_state: String,
+
+    #[builder(default = false)]
+    exportable: bool,
+
+    #[builder(setter(into), default)]
+    class: String,
+}
+
+impl<R> Props for DataTableProps<R> {
+    type Builder = DataTablePropsBuilder<R>;
+    fn builder() -> Self::Builder {
+        DataTableProps::builder()
+    }
+}
+
+#[allow(non_snake_case)]
+pub async fn DataTable<R: Sync>(props: DataTableProps<R>) -> String {
+    let target = format!("#{}", props.id);
+    let col_count = props.columns.len();
+
+    let columns = &props.columns;
+    let body = if props.rows.is_empty() {
+        html! {
+            <tr>
+                <td colspan=col_count class="px-3 py-10 text-center text-sm text-gray-500">
+                    {props.empty_state}
+                </td>
+            </tr>
+        }
+    } else {
+        props
+            .rows
+            .iter()
+            .map(|row| async move {
+                let cells = columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| async move {
+                        let color = if i == 0 {
+                            "font-medium text-gray-900"
+                        } else {
+                            "text-gray-500"
+                        };
+
+                        html! {
+                            <td class=format!(
+                                "whitespace-nowrap py-4 text-sm {} {} {} {}",
+                                padding_class(i, col_count),
+                                column.align.class(),
+                                color,
+                                column.width.as_deref().unwrap_or_default(),
+                            ).trim()>
+                                {column.render(row).await}
+                            </td>
+                        }
+                    })
+                    .collect_fragment_async()
+                    .await;
+
+                html! {
+                    <tr data-loading-states>{cells}</tr>
+                }
+            })
+            .collect_fragment_async()
+            .await
+    };
+
+    html! {
+        <div
+            id=props.id.clone()
+            class=format!("overflow-hidden shadow ring-1 ring-black ring-opacity-5 sm:rounded-lg {}", props.class).trim()
+            data-data-table
+        >
+            <div class="overflow-x-auto">
+                <table class="min-w-full divide-y divide-gray-300">
+                    <thead class="bg-gray-50">
+                        <tr>
+                            {
+                                props.columns.iter().enumerate().map(|(i, column)| {
+                                    let th_class = format!(
+                                        "py-3.5 text-sm font-semibold text-gray-900 {} {} {}",
+                                        padding_class(i, col_count),
+                                        column.align.class(),
+                                        column.width.as_deref().unwrap_or_default(),
+                                    );
+
+                                    match &column.sort_key {
+                                        Some(sort_key) => {
+                                            let is_sorted = props.query.is_sorted_by(sort_key);
+                                            let aria_sort = match (is_sorted, props.query.dir) {
+                                                (false, _) => "none",
+                                                (true, SortDirection::Asc) => "ascending",
+                                                (true, SortDirection::Desc) => "descending",
+                                            };
+
+                                            html! {
+                                                <th scope="col" class=th_class.trim() aria-sort=aria_sort>
+                                                    <a
+                                                        class="group inline-flex cursor-pointer"
+                                                        hx-get=props.query.sorted_by(sort_key).to_url(&props.url)
+                                                        hx-target=target.clone()
+                                                        hx-swap="outerHTML"
+                                                        hx-push-url="true"
+                                                    >
+                                                        {column.header.clone()}
+                                                        {sort_icon(is_sorted, props.query.dir)}
+                                                    </a>
+                                                </th>
+                                            }
+                                        }
+                                        None => html! {
+                                            <th scope="col" class=th_class.trim()>{column.header.clone()}</th>
+                                        },
+                                    }
+                                }).collect_fragment()
+                            }
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-gray-200 bg-white">
+                        {body}
+                    </tbody>
+                </table>
+            </div>
+            <Pagination
+                query=props.query
+                total=props.total
+                url=props.url
+                target=target
+                exportable=props.exportable
+            />
+        </div>
+    }
+}
+
+fn sort_icon(sorted: bool, dir: SortDirection) -> String {
+    let (class, path) = match (sorted, dir) {
+        (true, SortDirection::Desc) => (
+            "ml-2 flex-none rounded bg-gray-100 text-gray-900 group-hover:bg-gray-200",
+            "M5.23 7.21a.75.75 0 011.06.02L10 11.168l3.71-3.938a.75.75 0 111.08 1.04l-4.25 4.5a.75.75 0 01-1.08 0l-4.25-4.5a.75.75 0 01.02-1.06z",
+        ),
+        (true, SortDirection::Asc) => (
+            "ml-2 flex-none rounded bg-gray-100 text-gray-900 group-hover:bg-gray-200",
+            "M14.77 12.79a.75.75 0 01-1.06-.02L10 8.832 6.29 12.77a.75.75 0 11-1.08-1.04l4.25-4.5a.75.75 0 011.08 0l4.25 4.5a.75.75 0 01-.02 1.06z",
+        ),
+        (false, _) => (
+            "invisible ml-2 flex-none rounded text-gray-400 group-hover:visible group-focus:visible",
+            "M5.23 7.21a.75.75 0 011.06.02L10 11.168l3.71-3.938a.75.75 0 111.08 1.04l-4.25 4.5a.75.75 0 01-1.08 0l-4.25-4.5a.75.75 0 01.02-1.06z",
+        ),
+    };
+
+    html! {
+        <span class=class>
+            <svg class="h-5 w-5" viewBox="0 0 20 20" fill="currentColor" aria-hidden="true">
+                <path fill-rule="evenodd" d=path clip-rule="evenodd" />
+            </svg>
+        </span>
+    }
+}
+
+#[component]
+fn Pagination(
+    query: TableQuery,
+    total: usize,
+    url: String,
+    target: String,
+    exportable: bool,
+) -> String {
+    let page_count = query.page_count(total);
+    let from = if total == 0 { 0 } else { query.offset() + 1 };
+    let to = (query.offset() + query.per_page).min(total);
+
+    let page_link = |page: usize, enabled: bool| {
+        if enabled {
+            Attrs::with("hx-get", query.with_page(page).to_url(&url))
+                .set("hx-target", target.clone())
+                .set("hx-swap", "outerHTML".into())
+                .set("hx-push-url", "true".into())
+        } else {
+            Attrs::with("disabled", "true".into())
+        }
+    };
+    let page_class = |enabled: bool| {
+        if enabled {
+            ""
+        } else {
+            "cursor-not-allowed opacity-50"
+        }
+    };
+
+    html! {
+        <nav class="flex items-center justify-between border-t border-gray-200 bg-white px-4 py-3 sm:px-6" aria-label="Pagination">
+            <div class="hidden sm:block">
+                <p class="text-sm text-gray-700">
+                    "Showing " <span class="font-medium">{from}</span>
+                    " to " <span class="font-medium">{to}</span>
+                    " of " <span class="font-medium">{total}</span> " results"
+                </p>
+                {
+                    if exportable {
+                        html! {
+                            <p class="mt-1 text-sm text-gray-500">
+                                "Export page as "
+                                {export_link(&url, &query, ExportFormat::Csv, ExportScope::Page)}
+                                " / "
+                                {export_link(&url, &query, ExportFormat::Json, ExportScope::Page)}
+                                ", all rows as "
+                                {export_link(&url, &query, ExportFormat::Csv, ExportScope::All)}
+                                " / "
+                                {export_link(&url, &query, ExportFormat::Json, ExportScope::All)}
+                            </p>
+                        }
+                    } else {
+                        String::new()
+                    }
+                }
+            </div>
+            <div class="flex flex-1 justify-between gap-3 sm:justify-end">
+                <SecondaryButton
+                    class=page_class(query.page > 1)
+                    attrs=page_link(query.page.saturating_sub(1), query.page > 1)
+                >
+                    Previous
+                </SecondaryButton>
+                <SecondaryButton
+                    class=page_class(query.page < page_count)
+                    attrs=page_link(query.page + 1, query.page < page_count)
+                >
+                    Next
+                </SecondaryButton>
+            </div>
+        </nav>
+    }
+}
+
+fn export_link(url: &str, query: &TableQuery, format: ExportFormat, scope: ExportScope) -> String {
+    html! {
+        <a
+            class="font-medium text-indigo-600 hover:text-indigo-500"
+            href=query.with_export(format, scope).to_url(url)
+            hx-boost="false"
+            download
+        >
+            {format.as_str().to_uppercase()}
+        </a>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::unescape;
+
+    struct User {
+        name: String,
+    }
+
+    fn users(count: usize) -> Vec<User> {
+        (1..=count)
+            .map(|i| User {
+                name: format!("User {}", i),
+            })
+            .collect()
+    }
+
+    async fn render(rows: Vec<User>, query: TableQuery, total: usize) -> String {
+        DataTable(
+            DataTableProps::builder()
+                .id("users")
+                .columns(vec![
+                    Column::new("Name", |user: &User| user.name.clone()).sortable("name"),
+                    Column::new("Role", |_: &User| "Member".into()),
+                ])
+                .rows(rows)
+                .query(query)
+                .total(total)
+                .url("/users?status=active")
+                .exportable(true)
+                .build(),
+        )
+        .await
+    }
+
+    async fn data_table(rows: Vec<User>, query: TableQuery, total: usize) -> String {
+        unescape(render(rows, query, total).await)
+    }
+
+    #[tokio::test]
+    async fn test_sortable_headers() {
+        let html = data_table(users(2), TableQuery::default().sorted_by("name"), 2).await;
+
+        assert!(html.contains("aria-sort=\"ascending\""));
+        assert!(html.contains("hx-get=\"/users?status=active&sort=name&dir=desc&page=1\""));
+        // Only sortable columns get aria-sort.
+        assert_eq!(html.matches("aria-sort").count(), 1);
+
+        let html = data_table(users(2), TableQuery::default(), 2).await;
+        assert!(html.contains("aria-sort=\"none\""));
+        assert!(html.contains("hx-get=\"/users?status=active&sort=name&dir=asc&page=1\""));
+    }
+
+    #[tokio::test]
+    async fn test_escapes_cell_values() {
+        let rows = vec![User {
+            name: "<script>alert(1)</script>".into(),
+        }];
+        let html = render(rows, TableQuery::default(), 1).await;
+
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[tokio::test]
+    async fn test_empty_state() {
+        let html = data_table(vec![], TableQuery::default(), 0).await;
+
+        assert!(html.contains("colspan=\"2\""));
+        assert!(html.contains("No results found."));
+        assert!(!html.contains("data-loading-states"));
+    }
+
+    #[tokio::test]
+    async fn test_pagination_on_first_page() {
+        let html = data_table(users(25), TableQuery::default(), 60).await;
+
+        assert_eq!(html.matches("data-loading-states").count(), 25);
+        assert_eq!(html.matches("disabled=\"true\"").count(), 1);
+        assert!(html.contains("hx-get=\"/users?status=active&page=2\""));
+        assert!(html.contains("<span class=\"font-medium\">1</span>"));
+        assert!(html.contains("<span class=\"font-medium\">25</span>"));
+        assert!(html.contains("<span class=\"font-medium\">60</span>"));
+    }
+
+    #[tokio::test]
+    async fn test_pagination_on_last_page() {
+        let html = data_table(users(10), TableQuery::default().with_page(3), 60).await;
+
+        assert_eq!(html.matches("disabled=\"true\"").count(), 1);
+        assert!(html.contains("hx-get=\"/users?status=active&page=2\""));
+        assert!(!html.contains("page=4"));
+        assert!(html.contains("<span class=\"font-medium\">51</span>"));
+    }
+
+    #[tokio::test]
+    async fn test_export_links() {
+        let html = data_table(users(1), TableQuery::default(), 1).await;
+
+        assert!(html.contains("href=\"/users?status=active&page=1&export=csv&export_scope=page\""));
+        assert!(html.contains("href=\"/users?status=active&page=1&export=json&export_scope=all\""));
+    }
+}
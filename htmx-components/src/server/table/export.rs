@@ -0,0 +1,327 @@
+use std::convert::Infallible;
+use std::future::ready;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::header::{HeaderName, CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use futures_util::{stream, Stream, StreamExt};
+
+use super::data_table::Column;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn from_param(param: &str) -> Option<Self> {
+        match param {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+        }
+    }
+
+    fn head<R>(&self, columns: &[Column<R>]) -> String {
+        match self {
+            ExportFormat::Csv => csv_line(columns.iter().map(|column| column.header().to_string())),
+            ExportFormat::Json => "[".into(),
+        }
+    }
+
+    fn row<R>(&self, columns: &[Column<R>], row: &R, index: usize) -> String {
+        match self {
+            ExportFormat::Csv => csv_line(columns.iter().map(|column| column.value(row))),
+            ExportFormat::Json => {
+                let fields = columns
+                    .iter()
+                    .map(|column| {
+                        format!(
+                            "{}:{}",
+                            serde_json::Value::from(column.export_key()),
+                            serde_json::Value::from(column.value(row))
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                let separator = if index == 0 { "" } else { "," };
+                format!("{}{{{}}}", separator, fields)
+            }
+        }
+    }
+
+    fn tail(&self) -> String {
+        match self {
+            ExportFormat::Csv => "".into(),
+            ExportFormat::Json => "]".into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportScope {
+    // Only the rows of the current page.
+    Page,
+    // Every row matching the current sort, across all pages.
+    All,
+}
+
+impl ExportScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportScope::Page => "page",
+            ExportScope::All => "all",
+        }
+    }
+}
+
+// Spreadsheets evaluate fields starting with these as formulas.
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+// RFC 4180: quote fields containing separators, quotes or line breaks.
+// Fields that would be read as formulas are prefixed with "'" (so "-5" exports
+// as "'-5"), keeping exported user input from running in a spreadsheet.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+// Rows keyed by the same JSON key would silently drop a field.
+fn assert_unique_keys<R>(format: ExportFormat, columns: &[Column<R>]) {
+    if format != ExportFormat::Json {
+        return;
+    }
+
+    for (i, column) in columns.iter().enumerate() {
+        let key = column.export_key();
+        assert!(
+            columns[..i].iter().all(|other| other.export_key() != key),
+            "columns share the JSON export key {:?}, set a distinct Column::key",
+            key
+        );
+    }
+}
+
+fn csv_line(values: impl Iterator<Item = String>) -> String {
+    let mut line = values
+        .map(|value| csv_field(&value))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+fn attachment_headers(format: ExportFormat, filename: &str) -> [(HeaderName, String); 2] {
+    let filename = filename.replace(['"', '\\', '\r', '\n'], "");
+
+    [
+        (CONTENT_TYPE, format.content_type().to_string()),
+        (
+            CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.{}\"", filename, format.as_str()),
+        ),
+    ]
+}
+
+/**
+ * export_rows
+ *
+ * Serializes `rows` with the plain text `value` of each `Column`, so the
+ * export holds the same fields a `DataTable` with these columns shows.
+ * JSON rows are objects keyed by `Column::key`, the header by default, and
+ * columns sharing a key panic. CSV fields that would be read as spreadsheet
+ * formulas (starting with `=`, `+`, `-`, `@`...) are prefixed with `'`.
+ */
+pub fn export_rows<R>(format: ExportFormat, columns: &[Column<R>], rows: &[R]) -> String {
+    assert_unique_keys(format, columns);
+
+    let mut body = format.head(columns);
+    for (i, row) in rows.iter().enumerate() {
+        body.push_str(&format.row(columns, row, i));
+    }
+    body.push_str(&format.tail());
+    body
+}
+
+/**
+ * export_response
+ *
+ * Responds with `rows` as a `filename.csv` / `filename.json` download.
+ * Use it to export what has already been loaded, like the current page.
+ */
+pub fn export_response<R>(
+    format: ExportFormat,
+    filename: &str,
+    columns: &[Column<R>],
+    rows: &[R],
+) -> Response {
+    (
+        attachment_headers(format, filename),
+        export_rows(format, columns, rows),
+    )
+        .into_response()
+}
+
+/**
+ * export_stream_response
+ *
+ * Like `export_response`, but writes each row as it comes out of the `rows`
+ * stream, so a full result set never has to be held in memory.
+ */
+pub fn export_stream_response<R, S>(
+    format: ExportFormat,
+    filename: &str,
+    columns: Vec<Column<R>>,
+    rows: S,
+) -> Response
+where
+    R: Send + 'static,
+    S: Stream<Item = R> + Send + 'static,
+{
+    assert_unique_keys(format, &columns);
+
+    let columns = Arc::new(columns);
+
+    let head = stream::once(ready(format.head(&columns)));
+    let body = rows.enumerate().map({
+        let columns = columns.clone();
+        move |(i, row)| format.row(&columns, &row, i)
+    });
+    let tail = stream::once(ready(format.tail()));
+
+    let chunks = head.chain(body).chain(tail).map(Ok::<_, Infallible>);
+
+    (
+        attachment_headers(format, filename),
+        Body::from_stream(chunks),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct User {
+        name: &'static str,
+        bio: &'static str,
+    }
+
+    fn columns() -> Vec<Column<User>> {
+        vec![
+            Column::new("Name", |user: &User| user.name.to_string()),
+            Column::new("Bio", |user: &User| user.bio.to_string()),
+        ]
+    }
+
+    fn users() -> Vec<User> {
+        vec![
+            User {
+                name: "Mary",
+                bio: "Likes \"quotes\", commas",
+            },
+            User {
+                name: "Bob",
+                bio: "Plain",
+            },
+        ]
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_csv_field_formula_guard() {
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("-5"), "'-5");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("\tcmd"), "'\tcmd");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+
+    #[test]
+    fn test_export_rows_csv() {
+        assert_eq!(
+            export_rows(ExportFormat::Csv, &columns(), &users()),
+            "Name,Bio\r\nMary,\"Likes \"\"quotes\"\", commas\"\r\nBob,Plain\r\n"
+        );
+    }
+
+    #[test]
+    fn test_export_rows_json() {
+        let json = export_rows(ExportFormat::Json, &columns(), &users());
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!([
+                { "Name": "Mary", "Bio": "Likes \"quotes\", commas" },
+                { "Name": "Bob", "Bio": "Plain" },
+            ])
+        );
+        assert_eq!(export_rows(ExportFormat::Json, &columns(), &[]), "[]");
+    }
+
+    #[test]
+    fn test_export_rows_json_with_keys() {
+        let columns = vec![
+            Column::new("Name", |user: &User| user.name.to_string()).key("name"),
+            Column::new("Name", |user: &User| user.bio.to_string()).key("bio"),
+        ];
+        let json = export_rows(ExportFormat::Json, &columns, &users()[1..]);
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!([{ "name": "Bob", "bio": "Plain" }])
+        );
+        // Duplicate headers are fine in CSV.
+        assert_eq!(
+            export_rows(ExportFormat::Csv, &columns, &users()[1..]),
+            "Name,Name\r\nBob,Plain\r\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "columns share the JSON export key \"Name\"")]
+    fn test_export_rows_json_rejects_duplicate_keys() {
+        let columns = vec![
+            Column::new("Name", |user: &User| user.name.to_string()),
+            Column::new("Name", |user: &User| user.bio.to_string()),
+        ];
+
+        export_rows(ExportFormat::Json, &columns, &users());
+    }
+}
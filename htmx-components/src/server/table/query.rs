@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use axum::{async_trait, extract::FromRequestParts, extract::Query, http::request::Parts};
+
+use super::export::{ExportFormat, ExportScope};
+
+const DEFAULT_PER_PAGE: usize = 25;
+const MAX_PER_PAGE: usize = 100;
+
+// Params owned by `TableQuery`, replaced rather than duplicated by `to_url`.
+const QUERY_KEYS: [&str; 6] = ["sort", "dir", "page", "per_page", "export", "export_scope"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            SortDirection::Asc => SortDirection::Desc,
+            SortDirection::Desc => SortDirection::Asc,
+        }
+    }
+}
+
+// A requested export, read from the `export` and `export_scope` query params.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TableExport {
+    pub format: ExportFormat,
+    pub scope: ExportScope,
+}
+
+/**
+ * TableQuery
+ *
+ * Sort and pagination state of a `DataTable`, read from the `sort`, `dir`,
+ * `page` and `per_page` query params. Missing or malformed params fall back
+ * to their defaults, so extracting a `TableQuery` never fails.
+ * `export` is set when the request asks for a CSV or JSON download instead.
+ */
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableQuery {
+    pub sort: Option<String>,
+    pub dir: SortDirection,
+    pub page: usize,
+    pub per_page: usize,
+    pub export: Option<TableExport>,
+}
+
+impl Default for TableQuery {
+    fn default() -> Self {
+        Self {
+            sort: None,
+            dir: SortDirection::Asc,
+            page: 1,
+            per_page: DEFAULT_PER_PAGE,
+            export: None,
+        }
+    }
+}
+
+impl TableQuery {
+    pub fn from_params(params: &HashMap<String, String>) -> Self {
+        let default = Self::default();
+
+        Self {
+            sort: params
+                .get("sort")
+                .filter(|sort| !sort.is_empty())
+                .map(|sort| sort.to_string()),
+            dir: match params.get("dir").map(|dir| dir.as_str()) {
+                Some("desc") => SortDirection::Desc,
+                _ => SortDirection::Asc,
+            },
+            page: params
+                .get("page")
+                .and_then(|page| page.parse::<usize>().ok())
+                .filter(|page| *page > 0)
+                .unwrap_or(default.page),
+            per_page: params
+                .get("per_page")
+                .and_then(|per_page| per_page.parse::<usize>().ok())
+                .filter(|per_page| *per_page > 0)
+                .map(|per_page| per_page.min(MAX_PER_PAGE))
+                .unwrap_or(default.per_page),
+            export: params
+                .get("export")
+                .and_then(|format| ExportFormat::from_param(format))
+                .map(|format| TableExport {
+                    format,
+                    scope: match params.get("export_scope").map(|scope| scope.as_str()) {
+                        Some("all") => ExportScope::All,
+                        _ => ExportScope::Page,
+                    },
+                }),
+        }
+    }
+
+    // `page` and `per_page` are public, so guard against hand-built zero values.
+    pub fn offset(&self) -> usize {
+        self.page.saturating_sub(1) * self.per_page
+    }
+
+    pub fn page_count(&self, total: usize) -> usize {
+        total.div_ceil(self.per_page.max(1)).max(1)
+    }
+
+    pub fn is_sorted_by(&self, sort_key: &str) -> bool {
+        self.sort.as_deref() == Some(sort_key)
+    }
+
+    // Query to sort by the given key. Sorting by the current key flips the direction.
+    pub fn sorted_by(&self, sort_key: &str) -> Self {
+        let dir = if self.is_sorted_by(sort_key) {
+            self.dir.toggle()
+        } else {
+            SortDirection::Asc
+        };
+
+        Self {
+            sort: Some(sort_key.to_string()),
+            dir,
+            page: 1,
+            per_page: self.per_page,
+            export: None,
+        }
+    }
+
+    pub fn with_page(&self, page: usize) -> Self {
+        Self {
+            page: page.max(1),
+            export: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_export(&self, format: ExportFormat, scope: ExportScope) -> Self {
+        Self {
+            export: Some(TableExport { format, scope }),
+            ..self.clone()
+        }
+    }
+
+    pub fn to_query_string(&self) -> String {
+        let mut params = form_urlencoded::Serializer::new(String::new());
+
+        if let Some(sort) = &self.sort {
+            params.append_pair("sort", sort);
+            params.append_pair("dir", self.dir.as_str());
+        }
+        params.append_pair("page", &self.page.max(1).to_string());
+        if self.per_page != DEFAULT_PER_PAGE {
+            params.append_pair("per_page", &self.per_page.max(1).to_string());
+        }
+        if let Some(export) = &self.export {
+            params.append_pair("export", export.format.as_str());
+            params.append_pair("export_scope", export.scope.as_str());
+        }
+
+        params.finish()
+    }
+
+    // Links `url` with these params, keeping any other params `url` already has
+    // (e.g. the filters of the listed rows).
+    pub fn to_url(&self, url: &str) -> String {
+        let (url, fragment) = match url.split_once('#') {
+            Some((url, fragment)) => (url, Some(fragment)),
+            None => (url, None),
+        };
+        let (path, existing) = url.split_once('?').unwrap_or((url, ""));
+
+        let mut params = form_urlencoded::Serializer::new(String::new());
+        params.extend_pairs(
+            form_urlencoded::parse(existing.as_bytes())
+                .filter(|(key, _)| !QUERY_KEYS.contains(&key.as_ref())),
+        );
+        let mut params = params.finish();
+        if !params.is_empty() {
+            params.push('&');
+        }
+        params.push_str(&self.to_query_string());
+
+        match fragment {
+            Some(fragment) => format!("{}?{}#{}", path, params, fragment),
+            None => format!("{}?{}", path, params),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for TableQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let params = Query::<HashMap<String, String>>::try_from_uri(&parts.uri)
+            .map(|Query(params)| params)
+            .unwrap_or_default();
+
+        Ok(Self::from_params(&params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_params_with_no_params() {
+        assert_eq!(TableQuery::from_params(&params(&[])), TableQuery::default());
+    }
+
+    #[test]
+    fn test_from_params_with_all_params() {
+        assert_eq!(
+            TableQuery::from_params(&params(&[
+                ("sort", "name"),
+                ("dir", "desc"),
+                ("page", "3"),
+                ("per_page", "10"),
+            ])),
+            TableQuery {
+                sort: Some("name".into()),
+                dir: SortDirection::Desc,
+                page: 3,
+                per_page: 10,
+                export: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_params_with_invalid_params() {
+        assert_eq!(
+            TableQuery::from_params(&params(&[
+                ("sort", ""),
+                ("dir", "sideways"),
+                ("page", "0"),
+                ("per_page", "lots"),
+            ])),
+            TableQuery::default()
+        );
+    }
+
+    #[test]
+    fn test_from_params_with_export() {
+        let query = TableQuery::from_params(&params(&[("export", "csv")]));
+        assert_eq!(
+            query.export,
+            Some(TableExport {
+                format: ExportFormat::Csv,
+                scope: ExportScope::Page,
+            })
+        );
+
+        let query =
+            TableQuery::from_params(&params(&[("export", "json"), ("export_scope", "all")]));
+        assert_eq!(
+            query.export,
+            Some(TableExport {
+                format: ExportFormat::Json,
+                scope: ExportScope::All,
+            })
+        );
+
+        let query = TableQuery::from_params(&params(&[("export", "xlsx")]));
+        assert_eq!(query.export, None);
+    }
+
+    #[test]
+    fn test_from_params_clamps_per_page() {
+        let query = TableQuery::from_params(&params(&[("per_page", "5000")]));
+
+        assert_eq!(query.per_page, MAX_PER_PAGE);
+    }
+
+    #[test]
+    fn test_sorted_by_toggles_direction_and_resets_page() {
+        let query = TableQuery::default().with_page(4).sorted_by("name");
+        assert_eq!(query.dir, SortDirection::Asc);
+        assert_eq!(query.page, 1);
+
+        let query = query.sorted_by("name");
+        assert_eq!(query.dir, SortDirection::Desc);
+
+        let query = query.sorted_by("email");
+        assert_eq!(query.dir, SortDirection::Asc);
+    }
+
+    #[test]
+    fn test_offset_and_page_count() {
+        let query = TableQuery::default().with_page(3);
+
+        assert_eq!(query.offset(), 50);
+        assert_eq!(query.page_count(0), 1);
+        assert_eq!(query.page_count(51), 3);
+    }
+
+    #[test]
+    fn test_offset_and_page_count_with_zero_values() {
+        let query = TableQuery {
+            page: 0,
+            per_page: 0,
+            ..TableQuery::default()
+        };
+
+        assert_eq!(query.offset(), 0);
+        assert_eq!(query.page_count(10), 10);
+        assert_eq!(query.to_query_string(), "page=1&per_page=1");
+    }
+
+    #[test]
+    fn test_to_query_string_encodes_sort() {
+        let query = TableQuery::from_params(&params(&[("sort", "name&export=csv")]));
+
+        assert_eq!(
+            query.to_query_string(),
+            "sort=name%26export%3Dcsv&dir=asc&page=1"
+        );
+    }
+
+    #[test]
+    fn test_to_query_string() {
+        assert_eq!(TableQuery::default().to_query_string(), "page=1");
+        assert_eq!(
+            TableQuery::default().sorted_by("name").to_query_string(),
+            "sort=name&dir=asc&page=1"
+        );
+        assert_eq!(
+            TableQuery::default()
+                .with_export(ExportFormat::Csv, ExportScope::All)
+                .to_query_string(),
+            "page=1&export=csv&export_scope=all"
+        );
+    }
+
+    #[test]
+    fn test_to_url() {
+        let query = TableQuery::default().sorted_by("name");
+
+        assert_eq!(query.to_url("/users"), "/users?sort=name&dir=asc&page=1");
+        assert_eq!(
+            query.to_url("/users?status=active&page=3"),
+            "/users?status=active&sort=name&dir=asc&page=1"
+        );
+        assert_eq!(
+            query.to_url("/users?q=a%26b#list"),
+            "/users?q=a%26b&sort=name&dir=asc&page=1#list"
+        );
+    }
+}
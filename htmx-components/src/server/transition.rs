@@ -2,24 +2,141 @@ use super::html_element::HtmlElement;
 use rscx::{component, html, props};
 use rscx_web_macros::*;
 
+// Added to every transition so it degrades to instant show/hide for users
+// who prefer reduced motion.
+const REDUCED_MOTION: &str = "motion-reduce:transition-none";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TransitionDuration {
+    Fast,
+    #[default]
+    Normal,
+    Slow,
+}
+
+impl TransitionDuration {
+    fn enter(&self) -> &'static str {
+        match self {
+            TransitionDuration::Fast => "duration-150",
+            TransitionDuration::Normal => "duration-300",
+            TransitionDuration::Slow => "duration-500 sm:duration-700",
+        }
+    }
+
+    fn leave(&self) -> &'static str {
+        match self {
+            TransitionDuration::Fast => "duration-75",
+            TransitionDuration::Normal => "duration-200",
+            TransitionDuration::Slow => "duration-500 sm:duration-700",
+        }
+    }
+}
+
+/**
+ * TransitionPreset
+ *
+ * Common enter/leave transitions. `classes` expands a preset into the six
+ * class strings a `Transition` takes.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransitionPreset {
+    Fade,
+    Scale,
+    SlideFromTop,
+    SlideFromRight,
+    SlideFromBottom,
+    SlideFromLeft,
+    Collapse,
+    // Slides up on small screens, scales in from `sm` up. Used by `Modal`.
+    Dialog,
+    // Slides up on small screens, in from the right from `sm` up. Used by notifications.
+    Toast,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransitionClasses {
+    pub enter: String,
+    pub enter_from: String,
+    pub enter_to: String,
+    pub leave: String,
+    pub leave_from: String,
+    pub leave_to: String,
+}
+
+impl TransitionPreset {
+    pub fn classes(&self, duration: TransitionDuration) -> TransitionClasses {
+        let (property, enter_ease, leave_ease) = match self {
+            TransitionPreset::Fade => ("transition-opacity", "ease-out", "ease-in"),
+            TransitionPreset::SlideFromTop
+            | TransitionPreset::SlideFromRight
+            | TransitionPreset::SlideFromBottom
+            | TransitionPreset::SlideFromLeft
+            | TransitionPreset::Collapse => ("transition-all", "ease-in-out", "ease-in-out"),
+            _ => ("transition-all", "ease-out", "ease-in"),
+        };
+        let (hidden, shown) = match self {
+            TransitionPreset::Fade => ("opacity-0", "opacity-100"),
+            TransitionPreset::Scale => ("opacity-0 scale-95", "opacity-100 scale-100"),
+            TransitionPreset::SlideFromTop => ("-translate-y-full", "translate-y-0"),
+            TransitionPreset::SlideFromRight => ("translate-x-full", "translate-x-0"),
+            TransitionPreset::SlideFromBottom => ("translate-y-full", "translate-y-0"),
+            TransitionPreset::SlideFromLeft => ("-translate-x-full", "translate-x-0"),
+            TransitionPreset::Collapse => (
+                "max-h-0 opacity-0 overflow-hidden",
+                "max-h-screen opacity-100 overflow-hidden",
+            ),
+            TransitionPreset::Dialog => (
+                "opacity-0 translate-y-4 sm:translate-y-0 sm:scale-95",
+                "opacity-100 translate-y-0 sm:scale-100",
+            ),
+            TransitionPreset::Toast => (
+                "translate-y-2 opacity-0 sm:translate-y-0 sm:translate-x-2",
+                "translate-y-0 opacity-100 sm:translate-x-0",
+            ),
+        };
+
+        TransitionClasses {
+            enter: format!("{} {} {}", property, enter_ease, duration.enter()),
+            enter_from: hidden.into(),
+            enter_to: shown.into(),
+            leave: format!("{} {} {}", property, leave_ease, duration.leave()),
+            leave_from: shown.into(),
+            leave_to: hidden.into(),
+        }
+    }
+}
+
+/**
+ * Transition
+ *
+ * Shows and hides its children with CSS transitions. Pick a `preset` (and
+ * `duration`), or pass the six class strings yourself. Class strings given
+ * alongside a preset replace that part of the preset.
+ */
 #[html_element]
 pub struct TransitionProps {
-    #[builder(setter(into))]
+    #[builder(setter(into), default)]
+    preset: Option<TransitionPreset>,
+
+    #[builder(default)]
+    duration: TransitionDuration,
+
+    #[builder(setter(into), default)]
     enter: String,
 
-    #[builder(setter(into))]
+    #[builder(setter(into), default)]
     enter_from: String,
 
-    #[builder(setter(into))]
+    #[builder(setter(into), default)]
     enter_to: String,
 
-    #[builder(setter(into))]
+    #[builder(setter(into), default)]
     leave: String,
 
-    #[builder(setter(into))]
+    #[builder(setter(into), default)]
     leave_from: String,
 
-    #[builder(setter(into))]
+    #[builder(setter(into), default)]
     leave_to: String,
 
     #[builder(setter(into), default)]
@@ -31,6 +148,13 @@ pub struct TransitionProps {
 
 #[component]
 pub fn Transition(props: TransitionProps) -> String {
+    let preset = props
+        .preset
+        .map(|preset| preset.classes(props.duration))
+        .unwrap_or_default();
+    let pick = |raw: String, preset: String| if raw.is_empty() { preset } else { raw };
+    let with_reduced_motion = |classes: String| format!("{} {}", classes, REDUCED_MOTION);
+
     html! {
         <HtmlElement
             tag=props.tag
@@ -38,14 +162,45 @@ pub fn Transition(props: TransitionProps) -> String {
             component_name="Transition"
             attrs=spread_attrs!(props | omit(class))
                 .set("data-yc-control", "transition".into())
-                .set("data-transition-enter", props.enter)
-                .set("data-transition-enter-start", props.enter_from)
-                .set("data-transition-enter-end", props.enter_to)
-                .set("data-transition-leave", props.leave)
-                .set("data-transition-leave-start", props.leave_from)
-                .set("data-transition-leave-end", props.leave_to)
+                .set("data-transition-enter", with_reduced_motion(pick(props.enter, preset.enter)))
+                .set("data-transition-enter-start", pick(props.enter_from, preset.enter_from))
+                .set("data-transition-enter-end", pick(props.enter_to, preset.enter_to))
+                .set("data-transition-leave", with_reduced_motion(pick(props.leave, preset.leave)))
+                .set("data-transition-leave-start", pick(props.leave_from, preset.leave_from))
+                .set("data-transition-leave-end", pick(props.leave_to, preset.leave_to))
         >
             {props.children}
         </HtmlElement>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_preset_with_override() {
+        let html = html! {
+            <Transition
+                preset=TransitionPreset::SlideFromLeft
+                duration=TransitionDuration::Slow
+                leave_to="opacity-0"
+            />
+        };
+
+        assert!(html.contains("data-transition-enter-start=\"-translate-x-full\""));
+        assert!(html.contains("data-transition-leave-end=\"opacity-0\""));
+        assert!(html.contains(
+            "data-transition-enter=\"transition-all ease-in-out duration-500 sm:duration-700 motion-reduce:transition-none\""
+        ));
+    }
+
+    #[test]
+    fn test_fade_classes() {
+        let classes = TransitionPreset::Fade.classes(TransitionDuration::Fast);
+
+        assert_eq!(classes.enter, "transition-opacity ease-out duration-150");
+        assert_eq!(classes.leave, "transition-opacity ease-in duration-75");
+        assert_eq!(classes.enter_from, classes.leave_to);
+    }
+}
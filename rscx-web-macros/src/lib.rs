@@ -2,8 +2,9 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::{quote, ToTokens};
 use syn::{
-    parse::{Parse, ParseStream}, parse_macro_input, Fields,
-    FieldsNamed, Ident, ItemStruct, Token, ParenthesizedGenericArguments,
+    parse::{Parse, ParseStream},
+    parse_macro_input, DeriveInput, Fields, FieldsNamed, Ident, ItemStruct, LitStr,
+    ParenthesizedGenericArguments, Token,
 };
 
 const ATTRS_LEN: usize = 47;
@@ -251,3 +252,133 @@ fn transform_attrs(transform: Transformer, attr_keys: &mut Vec<&str>, attr_ident
         },
     }
 }
+
+/// Implements `htmx_components::server::table::TableRow` for a struct with named fields.
+///
+/// Every field becomes a column, in declaration order, unless it is skipped:
+///
+/// - `#[table_row(header = "Email address")]` overrides the heading (defaults to the field name).
+/// - `#[table_row(format = path::to_fn)]` formats the cell with `fn(&FieldType) -> String`
+///   (defaults to `ToString`).
+/// - `#[table_row(skip)]` hides the field from the table.
+///
+/// On the struct, `#[table_row(actions = path::to_fn)]` adds a trailing actions column
+/// rendered from `fn(&Self) -> Vec<TableRowAction>`, and `actions_header = "..."` sets its
+/// screen reader heading (defaults to "Actions").
+#[proc_macro_derive(TableRow, attributes(table_row))]
+pub fn derive_table_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match table_row(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn table_row(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut actions: Option<syn::Path> = None;
+    let mut actions_header = String::from("Actions");
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("table_row")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("actions") {
+                actions = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("actions_header") {
+                actions_header = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("expected `actions` or `actions_header`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct { fields: Fields::Named(named), .. }) => &named.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "TableRow can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut headings = vec![];
+    let mut cells = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let mut header = default_header(&ident.to_string());
+        let mut format: Option<syn::Path> = None;
+        let mut skip = false;
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("table_row")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("header") {
+                    header = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("format") {
+                    format = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `header`, `format` or `skip`"))
+                }
+            })?;
+        }
+
+        if skip {
+            continue;
+        }
+
+        headings.push(quote! {
+            ::htmx_components::server::table::TableHeading::title(#header)
+        });
+        cells.push(match format {
+            Some(format) => quote! { #format(&self.#ident) },
+            None => quote! { self.#ident.to_string() },
+        });
+    }
+
+    let (actions_heading, actions_fn) = match actions {
+        Some(actions) => (
+            Some(quote! {
+                ::htmx_components::server::table::TableHeading::empty(#actions_header)
+            }),
+            quote! {
+                fn table_actions(&self) -> Option<Vec<::htmx_components::server::table::TableRowAction>> {
+                    Some(#actions(self))
+                }
+            },
+        ),
+        None => (None, quote! {}),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::htmx_components::server::table::TableRow for #name #ty_generics #where_clause {
+            fn table_headings() -> ::htmx_components::server::table::TableHeadings {
+                vec![#(#headings,)* #actions_heading]
+            }
+
+            fn table_cells(&self) -> Vec<String> {
+                vec![#(#cells),*]
+            }
+
+            #actions_fn
+        }
+    })
+}
+
+// `created_at` => "Created at"
+fn default_header(field_name: &str) -> String {
+    let words = field_name.trim_start_matches("r#").replace('_', " ");
+    let mut chars = words.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}